@@ -68,8 +68,8 @@ fn main() {
     println!("\n=== File upload error handling ===");
     match FileUpload::from_path("/non/existent/file.txt") {
         Ok(_) => println!("File loaded successfully"),
-        Err(ReqwestBuilderError::IoError(msg)) => {
-            println!("File I/O error: {}", msg);
+        Err(ReqwestBuilderError::IoError { message, .. }) => {
+            println!("File I/O error: {}", message);
         }
         Err(e) => println!("Other error: {}", e),
     }
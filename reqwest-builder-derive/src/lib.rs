@@ -1,6 +1,6 @@
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{Data, DeriveInput, Fields, Lit, parse_macro_input};
+use syn::{Data, DeriveInput, Fields, Lit, ext::IdentExt, parse_macro_input};
 
 /// Derive macro for IntoReqwestBuilder
 ///
@@ -9,17 +9,82 @@ use syn::{Data, DeriveInput, Fields, Lit, parse_macro_input};
 /// # Attributes
 ///
 /// ## Container attributes (on the struct):
-/// - `#[request(method = "GET|POST|PUT|DELETE|PATCH")]` - HTTP method (required)
+/// - `#[request(method = "GET|POST|PUT|DELETE|PATCH")]` - HTTP method (required); extension
+///   methods such as `"PROPFIND"` or `"PURGE"` are also accepted
 /// - `#[request(path = "/endpoint")]` - Base endpoint path (required)
-/// - `#[request(body = "json|form|multipart|none")]` - Body type (optional, defaults to "json")
+/// - `#[request(body = "json|form|multipart|text|bytes|ndjson|none")]` - Body type (optional,
+///   defaults to "none" for GET and HEAD and to "json" otherwise)
+/// - `#[request(rename_all = "camelCase|snake_case|kebab-case|PascalCase")]` - Casing for query,
+///   header and multipart part names without an explicit `name` (optional)
+/// - `#[request(max_multipart_bytes = 10485760)]` - Reject multipart requests whose `#[file]`
+///   fields exceed this many bytes in total (optional)
+/// - `#[request(header("X-Api-Version" = "2"))]` - Send a constant header with every request;
+///   may be repeated. A `#[header]` field with the same name takes precedence (an
+///   `Option` field falls back to the constant when `None`)
+/// - `#[request(header_const("X-Client" = CLIENT_NAME))]` - Like `header`, with any constant
+///   `&str` expression such as a `const` item or `env!("CARGO_PKG_VERSION")`
+/// - `#[request(query = "serde")]` - Serialize the unmarked fields as the query with
+///   `serde_urlencoded`, honouring serde attributes; requires `body = "none"` and can't be
+///   combined with `#[query]` fields (`query = "fields"`, the default, uses `#[query]`)
+/// - `#[request(query = "qs")]` - Like `query = "serde"`, but with `serde_qs`, so nested structs
+///   and sequences become bracketed keys such as `filter[status]=active` (`serde_qs` feature)
+/// - `#[request(form_arrays = "json|repeat|brackets")]` - How array fields of a `form` body are
+///   sent: one JSON string (the default), `tags=a&tags=b` or `tags[]=a&tags[]=b`
+///
+/// Newtype structs such as `struct CreateUser(UserPayload)` are also supported: the
+/// single field is sent as the `json` or `form` body, and the path can't have
+/// placeholders.
 ///
 /// ## Field attributes:
-/// - `#[path_param]` - Include this field in the URL path (replaces `{field_name}` in path)
+/// - `#[path_param]` - Include this field in the URL path (replaces `{field_name}` in path,
+///   percent-encoded as a single path segment)
+/// - `#[path_param(name = "placeholder")]` - Replace `{placeholder}` instead of `{field_name}`
 /// - `#[query]` - Include this field as a query parameter
 /// - `#[query(name = "param_name")]` - Include as query parameter with custom name
+///   (`Vec<T>` fields emit one repeated parameter per element)
+/// - `#[query(required)]` - Fail with `QueryError` when building the request if the value is
+///   empty (or `None`)
+/// - `#[query(skip_if_none)]` - Omit the parameter when the value is `None` (the default)
+/// - `#[query(include_none)]` - Send `None` (or an empty `Vec`) as an empty value (`?flag=`);
+///   cannot be combined with `skip_if_none`
+/// - `#[query(style = "comma")]` - Send a list as one comma-joined parameter (`?ids=1,2,3`)
+///   instead of one repeated parameter per element (`style = "repeat"`, the default)
+/// - `#[query(flatten)]` - Hoist the parameters of a nested `IntoQueryParams` value (such as
+///   shared pagination options) into the query string
+/// - `#[query(raw)]` - The value is already percent-encoded; append it to the URL verbatim
+/// - `#[query(with = "path::to::fn")]` - Format the value with `fn(&FieldType) -> String`
+///   instead of `Display`
 /// - `#[header]` - Include this field as a header
 /// - `#[header(name = "header_name")]` - Include as header with custom name
-/// - `#[body]` - Include this field in the request body (default for unmarked fields)
+///   (any `Display` type; `Option<T>` headers are omitted when `None`, and
+///   `Vec<T>` headers send one value per element)
+/// - `#[header(sensitive)]` - Mark the header value as sensitive so it is redacted from
+///   `Debug` output and `debug_summary()`
+/// - `#[bearer]` - Send this field as `Authorization: Bearer {value}`
+/// - `#[basic_auth(username)]` / `#[basic_auth(password)]` - Combine these two fields into
+///   `Authorization: Basic {base64(username:password)}`
+/// - `#[body]` - Send this single field as the entire body (unmarked fields are combined
+///   into the body instead; the two can't be mixed)
+/// - `#[file]` - Send this `FileUpload` field as a file part (requires `body = "multipart"`;
+///   `Option<FileUpload>` fields are omitted when `None`)
+/// - `#[file(name = "part_name")]` - Send the file part under a custom form field name
+///   instead of the field identifier (text parts are renamed with `#[serde(rename)]`)
+///
+/// JSON and form bodies contain only the body fields, so path, query and header
+/// fields don't need `#[serde(skip)]`. Serde attributes on body fields and on the
+/// struct still apply to the body.
+///
+/// With `body = "multipart"` every other body field is sent as a text part.
+///
+/// Enums are supported too: each variant carries its own `#[request(...)]`
+/// (with a `json`, `form` or `none` body) and may use `#[path_param]` fields.
+/// The body is the enum's serde representation, so mark path fields
+/// `#[serde(skip)]` to keep them out of it.
+///
+/// With `body = "text"` the struct must have exactly one body field, whose
+/// `to_string()` value is sent as the plain-text body. With `body = "bytes"`
+/// the single body field must implement `AsRef<[u8]>` and is sent as-is. With
+/// `body = "ndjson"` each element of the single body field is sent as one JSON line.
 ///
 /// # Example
 ///
@@ -49,7 +114,7 @@ use syn::{Data, DeriveInput, Fields, Lit, parse_macro_input};
 /// ```
 #[proc_macro_derive(
     IntoReqwestBuilder,
-    attributes(request, path_param, query, header, body)
+    attributes(request, path_param, query, header, body, file, bearer, basic_auth)
 )]
 pub fn derive_into_reqwest_builder(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -61,13 +126,28 @@ pub fn derive_into_reqwest_builder(input: TokenStream) -> TokenStream {
 }
 
 fn impl_into_reqwest_builder(input: &DeriveInput) -> Result<proc_macro2::TokenStream, syn::Error> {
+    if let Data::Enum(data_enum) = &input.data {
+        return impl_enum_into_reqwest_builder(input, data_enum);
+    }
+    if let Data::Struct(syn::DataStruct {
+        fields: Fields::Unnamed(fields),
+        ..
+    }) = &input.data
+    {
+        return impl_newtype_into_reqwest_builder(input, fields);
+    }
+
     let name = &input.ident;
 
     // Parse container attributes
     let container_attrs = parse_container_attributes(&input.attrs)?;
     let method = container_attrs.method;
     let path = container_attrs.path;
-    let body_type = container_attrs.body_type;
+    let path_span = container_attrs.path_span;
+    let body_kind = container_attrs.body_kind;
+    let rename_all = container_attrs.rename_all;
+    let max_multipart_bytes = container_attrs.max_multipart_bytes;
+    let serde_query = container_attrs.serde_query;
 
     // Extract struct fields
     let fields = match &input.data {
@@ -80,46 +160,237 @@ fn impl_into_reqwest_builder(input: &DeriveInput) -> Result<proc_macro2::TokenSt
                 ));
             }
         },
-        _ => return Err(syn::Error::new_spanned(input, "Only structs are supported")),
+        _ => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "Only structs and enums are supported",
+            ));
+        }
     };
 
     // Analyze fields for different purposes
     let mut path_fields = Vec::new();
     let mut query_fields = Vec::new();
     let mut header_fields = Vec::new();
+    let mut sensitive_headers = Vec::new();
+    let mut body_fields = Vec::new();
+    let mut file_fields = Vec::new();
+    let mut text_fields = Vec::new();
+    let mut basic_auth_username = None;
+    let mut basic_auth_password = None;
+    let mut whole_body_fields = Vec::new();
 
     for field in fields {
         let field_name = field.ident.as_ref().unwrap();
         let field_attrs = parse_field_attributes(&field.attrs)?;
 
         match field_attrs.field_type {
-            FieldType::Path => {
-                path_fields.push(field_name);
+            FieldType::Path { name } => {
+                path_fields.push(PathField {
+                    ident: field_name,
+                    placeholder: name.unwrap_or_else(|| field_name.unraw().to_string()),
+                });
             }
-            FieldType::Query { name } => {
-                let param_name = name.unwrap_or_else(|| field_name.to_string());
-                query_fields.push((field_name, param_name));
+            FieldType::Query(options) => {
+                query_fields.push(QueryField {
+                    ident: field_name,
+                    param_name: options
+                        .name
+                        .unwrap_or_else(|| rename_field(field_name, rename_all)),
+                    include_none: options.include_none,
+                    required: options.required,
+                    comma: options.comma,
+                    raw: options.raw,
+                    flatten: options.flatten,
+                    with: options.with,
+                    unwrap_option: is_option_type(&field.ty),
+                });
             }
-            FieldType::Header { name } => {
-                let header_name = name.unwrap_or_else(|| field_name.to_string());
-                header_fields.push((field_name, header_name));
+            FieldType::Header { name, sensitive } => {
+                let header_name = name.unwrap_or_else(|| rename_field(field_name, rename_all));
+                if header_name.eq_ignore_ascii_case("Authorization") {
+                    check_single_authorization(field, &header_fields)?;
+                }
+                if sensitive {
+                    sensitive_headers.push(header_name.clone());
+                }
+                let optional = is_option_type(&field.ty);
+                let multiple = is_type_named(&field.ty, "Vec");
+                let value = if optional {
+                    quote! { self.#field_name.as_ref().map(|value| value.to_string()) }
+                } else if multiple {
+                    quote! { self.#field_name.iter().map(|value| value.to_string()).collect() }
+                } else {
+                    quote! { self.#field_name.to_string() }
+                };
+                header_fields.push(HeaderField {
+                    ident: field_name.clone(),
+                    header_name,
+                    optional,
+                    multiple,
+                    value,
+                });
             }
-            FieldType::Body => {
-                // Body fields are handled automatically by serde serialization
-                // We don't need to do anything special for them
+            FieldType::Bearer => {
+                check_single_authorization(field, &header_fields)?;
+                let optional = is_option_type(&field.ty);
+                let value = if optional {
+                    quote! { self.#field_name.as_ref().map(|value| format!("Bearer {}", value)) }
+                } else {
+                    quote! { format!("Bearer {}", self.#field_name) }
+                };
+                header_fields.push(HeaderField {
+                    ident: field_name.clone(),
+                    header_name: "Authorization".to_string(),
+                    optional,
+                    multiple: false,
+                    value,
+                });
+            }
+            FieldType::BasicAuth(role) => {
+                let slot = match role {
+                    BasicAuthRole::Username => &mut basic_auth_username,
+                    BasicAuthRole::Password => &mut basic_auth_password,
+                };
+                if slot.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        field,
+                        "Duplicate #[basic_auth] field for the same role",
+                    ));
+                }
+                *slot = Some(field);
             }
+            FieldType::Body { explicit } => {
+                // Body fields are handled automatically by serde serialization,
+                // we only track them for raw body types like text
+                if explicit {
+                    whole_body_fields.push(field);
+                }
+                body_fields.push(field_name);
+                text_fields.push(MultipartField {
+                    field,
+                    part_name: multipart_part_name(field, None, rename_all),
+                });
+            }
+            FieldType::File { name } => {
+                if !matches!(body_kind, BodyKind::Multipart) {
+                    return Err(syn::Error::new_spanned(
+                        field,
+                        "#[file] fields require body = \"multipart\"",
+                    ));
+                }
+                file_fields.push(MultipartField {
+                    field,
+                    part_name: multipart_part_name(field, name, rename_all),
+                });
+            }
+        }
+    }
+
+    // Combine basic auth credentials into a single Authorization header
+    match (basic_auth_username, basic_auth_password) {
+        (Some(username), Some(password)) => {
+            check_single_authorization(username, &header_fields)?;
+            let username_name = username.ident.as_ref().unwrap();
+            let password_name = password.ident.as_ref().unwrap();
+            let password_value = if is_option_type(&password.ty) {
+                quote! { self.#password_name.as_ref() }
+            } else {
+                quote! { Some(&self.#password_name) }
+            };
+            header_fields.push(HeaderField {
+                ident: quote::format_ident!("basic_auth"),
+                header_name: "Authorization".to_string(),
+                optional: false,
+                multiple: false,
+                value: quote! {
+                    reqwest_builder::basic_auth_value(&self.#username_name, #password_value)
+                },
+            });
+        }
+        (None, None) => {}
+        _ => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "#[basic_auth] requires both a `username` and a `password` field",
+            ));
         }
     }
 
+    // An explicit `#[body]` field is the entire body, so it must stand alone
+    let whole_body_field = match whole_body_fields.as_slice() {
+        [] => None,
+        [field] => {
+            if let Some(other) = body_fields
+                .iter()
+                .find(|other| **other != field.ident.as_ref().unwrap())
+            {
+                return Err(syn::Error::new_spanned(
+                    other,
+                    "Unmarked fields can't be combined with a #[body] field, which is the whole body",
+                ));
+            }
+            Some(*field)
+        }
+        [_, second, ..] => {
+            return Err(syn::Error::new_spanned(
+                second,
+                "Only one field can be marked #[body]",
+            ));
+        }
+    };
+
+    // Every placeholder needs a field and every path field a placeholder
+    validate_path_params(&path, path_span, &path_fields)?;
+
     // Generate the endpoint method with path substitution
     let endpoint_impl = generate_endpoint_impl(&path, &path_fields);
 
-    // Generate query params method
-    let query_params_impl = generate_query_params_impl(&query_fields);
+    // Generate query params method, in field declaration order, or from the
+    // unmarked fields with `query = "serde"`
+    let query_params_impl = if let Some(style) = serde_query {
+        if let Some(query_field) = query_fields.first() {
+            return Err(syn::Error::new_spanned(
+                query_field.ident,
+                format!(
+                    "#[query] fields can't be combined with `query = \"{}\"`, \
+                     which sends every unmarked field as a query parameter",
+                    style
+                ),
+            ));
+        }
+        if let Some(field) = whole_body_field {
+            return Err(syn::Error::new_spanned(
+                field,
+                format!(
+                    "#[body] fields can't be combined with `query = \"{}\"`",
+                    style
+                ),
+            ));
+        }
+        let query_field_defs: Vec<_> = fields
+            .iter()
+            .filter(|field| body_fields.contains(&field.ident.as_ref().unwrap()))
+            .collect();
+        generate_serde_query_impl(input, style, &query_field_defs)
+    } else {
+        generate_query_params_impl(&query_fields)
+    };
 
     // Generate headers method and Headers type
     let (headers_type, headers_impl, headers_struct_name) =
-        generate_headers_impl(name, &header_fields);
+        generate_headers_impl(name, &header_fields, &container_attrs.static_headers);
+
+    // Headers marked `sensitive`, on top of the trait's built-in list
+    let sensitive_headers_impl = if sensitive_headers.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            fn sensitive_headers(&self) -> &[&str] {
+                &[#(#sensitive_headers),*]
+            }
+        }
+    };
 
     // Generate the method implementation
     let method_impl = quote! {
@@ -129,12 +400,44 @@ fn impl_into_reqwest_builder(input: &DeriveInput) -> Result<proc_macro2::TokenSt
     };
 
     // Generate body type implementation
+    let body_type = body_kind.to_tokens();
     let body_impl = quote! {
         fn body(&self) -> ::reqwest_builder::RequestBody {
             #body_type
         }
     };
 
+    // Array encoding of form bodies keeps the trait's default otherwise
+    let form_array_style_impl = form_array_style_impl(container_attrs.form_arrays.as_ref());
+
+    // Generate raw body content implementation
+    let raw_body_impl = generate_raw_body_impl(input, &body_kind, &body_fields)?;
+
+    // Generate multipart form implementation
+    let multipart_impl =
+        generate_multipart_impl(&body_kind, &text_fields, &file_fields, max_multipart_bytes);
+
+    // Generate body serialization limited to the body fields
+    let serialize_body_impl = match whole_body_field {
+        Some(field) => {
+            let field_name = field.ident.as_ref().unwrap();
+            quote! {
+                fn serialize_body(
+                    &self,
+                ) -> std::result::Result<serde_json::Value, ::reqwest_builder::ReqwestBuilderError> {
+                    Ok(serde_json::to_value(&self.#field_name)?)
+                }
+            }
+        }
+        None => {
+            let body_field_defs: Vec<_> = fields
+                .iter()
+                .filter(|field| body_fields.contains(&field.ident.as_ref().unwrap()))
+                .collect();
+            generate_serialize_body_impl(input, &body_field_defs)
+        }
+    };
+
     Ok(quote! {
         #headers_type
 
@@ -146,10 +449,18 @@ fn impl_into_reqwest_builder(input: &DeriveInput) -> Result<proc_macro2::TokenSt
             #endpoint_impl
 
             #headers_impl
+            #sensitive_headers_impl
 
             #query_params_impl
 
             #body_impl
+            #form_array_style_impl
+
+            #raw_body_impl
+
+            #multipart_impl
+
+            #serialize_body_impl
         }
     })
 }
@@ -158,7 +469,139 @@ fn impl_into_reqwest_builder(input: &DeriveInput) -> Result<proc_macro2::TokenSt
 struct ContainerAttributes {
     method: proc_macro2::TokenStream,
     path: String,
-    body_type: proc_macro2::TokenStream,
+    path_span: proc_macro2::Span,
+    body_kind: BodyKind,
+    rename_all: Option<RenameRule>,
+    max_multipart_bytes: Option<u64>,
+    /// Constant `(name, value)` headers from `header("name" = "value")` and
+    /// `header_const("name" = EXPR)`; the value evaluates to a `&'static str`
+    static_headers: Vec<(String, proc_macro2::TokenStream)>,
+    /// `FormArrayStyle` variant from `form_arrays = "..."`
+    form_arrays: Option<proc_macro2::TokenStream>,
+    /// `query = "serde"` or `query = "qs"`: the unmarked fields are serialized
+    /// as the query with `serde_urlencoded` or `serde_qs`
+    serde_query: Option<&'static str>,
+}
+
+/// Casing applied to query, header and multipart part names without an explicit `name`
+#[derive(Debug, Clone, Copy)]
+enum RenameRule {
+    CamelCase,
+    SnakeCase,
+    KebabCase,
+    PascalCase,
+}
+
+impl RenameRule {
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "camelCase" => Some(RenameRule::CamelCase),
+            "snake_case" => Some(RenameRule::SnakeCase),
+            "kebab-case" => Some(RenameRule::KebabCase),
+            "PascalCase" => Some(RenameRule::PascalCase),
+            _ => None,
+        }
+    }
+
+    /// Apply the rule to a snake_case field name
+    fn apply(&self, field_name: &str) -> String {
+        let words = field_name.split('_').filter(|word| !word.is_empty());
+        match self {
+            RenameRule::SnakeCase => words.collect::<Vec<_>>().join("_"),
+            RenameRule::KebabCase => words.collect::<Vec<_>>().join("-"),
+            RenameRule::PascalCase => words.map(capitalize).collect(),
+            RenameRule::CamelCase => words
+                .enumerate()
+                .map(|(i, word)| {
+                    if i == 0 {
+                        word.to_string()
+                    } else {
+                        capitalize(word)
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn rename_field(field_name: &syn::Ident, rename_all: Option<RenameRule>) -> String {
+    // A raw identifier like `r#type` is named `type`
+    let name = field_name.unraw().to_string();
+    match rename_all {
+        Some(rule) => rule.apply(&name),
+        None => name,
+    }
+}
+
+/// Name of a multipart part: an explicit `name`, then `#[serde(rename)]`,
+/// then the field name under `rename_all`
+fn multipart_part_name(
+    field: &syn::Field,
+    name: Option<String>,
+    rename_all: Option<RenameRule>,
+) -> String {
+    name.or_else(|| serde_rename(&field.attrs))
+        .unwrap_or_else(|| rename_field(field.ident.as_ref().unwrap(), rename_all))
+}
+
+/// The `rename = "..."` of a field's `#[serde(...)]` attributes, if any
+fn serde_rename(attrs: &[syn::Attribute]) -> Option<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .find_map(|attr| {
+            // Malformed serde attributes are reported by serde itself
+            let metas = attr
+                .parse_args_with(
+                    syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated,
+                )
+                .ok()?;
+            metas.into_iter().find_map(|meta| match meta {
+                syn::Meta::NameValue(name_value) if name_value.path.is_ident("rename") => {
+                    match name_value.value {
+                        syn::Expr::Lit(syn::ExprLit {
+                            lit: Lit::Str(lit_str),
+                            ..
+                        }) => Some(lit_str.value()),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+        })
+}
+
+#[derive(Debug)]
+enum BodyKind {
+    Json,
+    Form,
+    Multipart,
+    Text,
+    Bytes,
+    Ndjson,
+    None,
+}
+
+impl BodyKind {
+    fn to_tokens(&self) -> proc_macro2::TokenStream {
+        match self {
+            BodyKind::Json => quote! { reqwest_builder::RequestBody::Json },
+            BodyKind::Form => quote! { reqwest_builder::RequestBody::Form },
+            BodyKind::Multipart => quote! { reqwest_builder::RequestBody::Multipart },
+            BodyKind::Text => quote! { reqwest_builder::RequestBody::Text },
+            BodyKind::Bytes => quote! { reqwest_builder::RequestBody::Bytes },
+            BodyKind::Ndjson => quote! { reqwest_builder::RequestBody::Ndjson },
+            BodyKind::None => quote! { reqwest_builder::RequestBody::None },
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -168,16 +611,99 @@ struct FieldAttributes {
 
 #[derive(Debug)]
 enum FieldType {
-    Path,
-    Query { name: Option<String> },
-    Header { name: Option<String> },
-    Body,
+    Path {
+        name: Option<String>,
+    },
+    Query(QueryOptions),
+    Header {
+        name: Option<String>,
+        sensitive: bool,
+    },
+    Bearer,
+    BasicAuth(BasicAuthRole),
+    /// An explicit `#[body]` field is the whole body; unmarked fields are combined
+    Body {
+        explicit: bool,
+    },
+    File {
+        name: Option<String>,
+    },
+}
+
+#[derive(Debug, Default)]
+struct QueryOptions {
+    name: Option<String>,
+    include_none: bool,
+    required: bool,
+    comma: bool,
+    raw: bool,
+    flatten: bool,
+    with: Option<proc_macro2::TokenStream>,
+}
+
+/// A field substituted into a `{placeholder}` of the path
+#[derive(Debug)]
+struct PathField<'a> {
+    ident: &'a syn::Ident,
+    placeholder: String,
+}
+
+/// A field sent as a multipart text or file part
+struct MultipartField<'a> {
+    field: &'a syn::Field,
+    /// Form field name of the part, in its `Content-Disposition`
+    part_name: String,
+}
+
+/// A field rendered as a query parameter
+#[derive(Debug)]
+struct QueryField<'a> {
+    ident: &'a syn::Ident,
+    param_name: String,
+    include_none: bool,
+    /// Reject the request when the parameter is missing or empty
+    required: bool,
+    /// Join list elements into one comma-separated value
+    comma: bool,
+    /// Append the value to the URL verbatim, without percent-encoding
+    raw: bool,
+    /// Hoist the parameters of an `IntoQueryParams` value into the query string
+    flatten: bool,
+    with: Option<proc_macro2::TokenStream>,
+    /// Dispatch the inner value of an `Option` field, so `Display`-only types
+    /// and comma-joined lists work inside it too
+    unwrap_option: bool,
+}
+
+#[derive(Debug)]
+enum BasicAuthRole {
+    Username,
+    Password,
+}
+
+/// A request header and the expression producing its value
+///
+/// `value` evaluates to `String`, `Option<String>` when `optional` is set, or
+/// `Vec<String>` when `multiple` is set.
+#[derive(Debug)]
+struct HeaderField {
+    ident: syn::Ident,
+    header_name: String,
+    optional: bool,
+    multiple: bool,
+    value: proc_macro2::TokenStream,
 }
 
 fn parse_container_attributes(attrs: &[syn::Attribute]) -> Result<ContainerAttributes, syn::Error> {
     let mut method = None;
     let mut path = None;
-    let mut body_type = quote! { reqwest_builder::RequestBody::Json }; // Default to JSON
+    let mut body_kind = None;
+    let mut bodiless_method = false;
+    let mut rename_all = None;
+    let mut max_multipart_bytes = None;
+    let mut static_headers = Vec::new();
+    let mut form_arrays = None;
+    let mut serde_query = None;
 
     for attr in attrs {
         if attr.path().is_ident("request") {
@@ -185,6 +711,7 @@ fn parse_container_attributes(attrs: &[syn::Attribute]) -> Result<ContainerAttri
                 if meta.path.is_ident("method") {
                     let value: Lit = meta.value()?.parse()?;
                     if let Lit::Str(lit_str) = value {
+                        bodiless_method = matches!(lit_str.value().as_str(), "GET" | "HEAD");
                         method = Some(match lit_str.value().as_str() {
                             "GET" => quote! { http::Method::GET },
                             "POST" => quote! { http::Method::POST },
@@ -193,31 +720,124 @@ fn parse_container_attributes(attrs: &[syn::Attribute]) -> Result<ContainerAttri
                             "PATCH" => quote! { http::Method::PATCH },
                             "HEAD" => quote! { http::Method::HEAD },
                             "OPTIONS" => quote! { http::Method::OPTIONS },
+                            // Extension methods such as PROPFIND or PURGE
+                            other if !is_http_token(other) => {
+                                return Err(syn::Error::new(
+                                    lit_str.span(),
+                                    format!(
+                                        "Invalid HTTP method {:?}: expected a non-empty token \
+                                         such as \"GET\" or \"PROPFIND\" without spaces or separators",
+                                        other
+                                    ),
+                                ));
+                            }
                             other => {
-                                return Err(
-                                    meta.error(format!("Unsupported HTTP method: {}", other))
-                                );
+                                let bytes = syn::LitByteStr::new(other.as_bytes(), lit_str.span());
+                                quote! {
+                                    http::Method::from_bytes(#bytes)
+                                        .expect("invalid HTTP method in #[request(method)]")
+                                }
                             }
                         });
                     }
                 } else if meta.path.is_ident("path") {
                     let value: Lit = meta.value()?.parse()?;
                     if let Lit::Str(lit_str) = value {
-                        path = Some(lit_str.value());
+                        path = Some((lit_str.value(), lit_str.span()));
                     }
                 } else if meta.path.is_ident("body") {
                     let value: Lit = meta.value()?.parse()?;
                     if let Lit::Str(lit_str) = value {
-                        body_type = match lit_str.value().as_str() {
-                            "json" => quote! { reqwest_builder::RequestBody::Json },
-                            "form" => quote! { reqwest_builder::RequestBody::Form },
-                            "multipart" => quote! { reqwest_builder::RequestBody::Multipart },
-                            "none" => quote! { reqwest_builder::RequestBody::None },
+                        body_kind = Some(match lit_str.value().as_str() {
+                            "json" => BodyKind::Json,
+                            "form" => BodyKind::Form,
+                            "multipart" => BodyKind::Multipart,
+                            "text" => BodyKind::Text,
+                            "bytes" => BodyKind::Bytes,
+                            "ndjson" => BodyKind::Ndjson,
+                            "none" => BodyKind::None,
                             other => {
                                 return Err(meta.error(format!("Unsupported body type: {}", other)));
                             }
-                        };
+                        });
                     }
+                } else if meta.path.is_ident("rename_all") {
+                    let value: Lit = meta.value()?.parse()?;
+                    if let Lit::Str(lit_str) = value {
+                        let rule = RenameRule::from_str(&lit_str.value()).ok_or_else(|| {
+                            meta.error(format!("Unsupported rename_all rule: {}", lit_str.value()))
+                        })?;
+                        rename_all = Some(rule);
+                    }
+                } else if meta.path.is_ident("form_arrays") {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    form_arrays = Some(match value.value().as_str() {
+                        "json" => quote! { ::reqwest_builder::FormArrayStyle::Json },
+                        "repeat" => quote! { ::reqwest_builder::FormArrayStyle::Repeat },
+                        "brackets" => quote! { ::reqwest_builder::FormArrayStyle::Brackets },
+                        other => {
+                            return Err(syn::Error::new_spanned(
+                                value,
+                                format!(
+                                    "Unsupported form_arrays style {:?}: expected \"json\", \
+                                     \"repeat\" or \"brackets\"",
+                                    other
+                                ),
+                            ));
+                        }
+                    });
+                } else if meta.path.is_ident("query") {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    serde_query = match value.value().as_str() {
+                        "fields" => None,
+                        "serde" => Some("serde"),
+                        "qs" if cfg!(feature = "serde_qs") => Some("qs"),
+                        "qs" => {
+                            return Err(syn::Error::new_spanned(
+                                value,
+                                "`query = \"qs\"` requires the `serde_qs` feature of reqwest-builder",
+                            ));
+                        }
+                        other => {
+                            return Err(syn::Error::new_spanned(
+                                value,
+                                format!(
+                                    "Unsupported query style {:?}: expected \"fields\", \"serde\" or \"qs\"",
+                                    other
+                                ),
+                            ));
+                        }
+                    };
+                } else if meta.path.is_ident("max_multipart_bytes") {
+                    let value: syn::LitInt = meta.value()?.parse()?;
+                    max_multipart_bytes = Some(value.base10_parse()?);
+                } else if meta.path.is_ident("header") {
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    let name: syn::LitStr = content.parse()?;
+                    content.parse::<syn::Token![=]>()?;
+                    let value: syn::LitStr = content.parse()?;
+                    if !is_http_token(&name.value()) {
+                        return Err(syn::Error::new_spanned(name, "Invalid header name"));
+                    }
+                    static_headers.push((name.value(), quote! { #value }));
+                } else if meta.path.is_ident("header_const") {
+                    // Any constant `&str` expression, e.g. `env!(...)` or a `const` item
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    let name: syn::LitStr = content.parse()?;
+                    content.parse::<syn::Token![=]>()?;
+                    let value: syn::Expr = content.parse()?;
+                    if !is_http_token(&name.value()) {
+                        return Err(syn::Error::new_spanned(name, "Invalid header name"));
+                    }
+                    static_headers.push((
+                        name.value(),
+                        quote! {{
+                            const VALUE: &str = #value;
+                            VALUE
+                        }},
+                    ));
                 }
                 Ok(())
             })?;
@@ -226,124 +846,846 @@ fn parse_container_attributes(attrs: &[syn::Attribute]) -> Result<ContainerAttri
 
     let method = method
         .ok_or_else(|| syn::Error::new_spanned(&attrs[0], "Missing required 'method' attribute"))?;
-    let path = path
+    let (path, path_span) = path
         .ok_or_else(|| syn::Error::new_spanned(&attrs[0], "Missing required 'path' attribute"))?;
+    // GET and HEAD requests have no body unless one is asked for; others default to JSON
+    let body_kind = body_kind.unwrap_or(if bodiless_method {
+        BodyKind::None
+    } else {
+        BodyKind::Json
+    });
+    if max_multipart_bytes.is_some() && !matches!(body_kind, BodyKind::Multipart) {
+        return Err(syn::Error::new_spanned(
+            &attrs[0],
+            "`max_multipart_bytes` requires body = \"multipart\"",
+        ));
+    }
+    if let Some(style) = serde_query
+        && !matches!(body_kind, BodyKind::None)
+    {
+        return Err(syn::Error::new_spanned(
+            &attrs[0],
+            format!(
+                "`query = \"{}\"` sends the unmarked fields as the query and requires body = \"none\"",
+                style
+            ),
+        ));
+    }
+    if form_arrays.is_some() && !matches!(body_kind, BodyKind::Form) {
+        return Err(syn::Error::new_spanned(
+            &attrs[0],
+            "`form_arrays` requires body = \"form\"",
+        ));
+    }
 
     Ok(ContainerAttributes {
         method,
         path,
-        body_type,
+        path_span,
+        body_kind,
+        rename_all,
+        max_multipart_bytes,
+        static_headers,
+        form_arrays,
+        serde_query,
     })
 }
 
 fn parse_field_attributes(attrs: &[syn::Attribute]) -> Result<FieldAttributes, syn::Error> {
     for attr in attrs {
         if attr.path().is_ident("path_param") {
-            return Ok(FieldAttributes {
-                field_type: FieldType::Path,
-            });
-        } else if attr.path().is_ident("query") {
             let mut name = None;
 
-            // Try to parse nested meta if the attribute has arguments
-            let _ = attr.parse_nested_meta(|meta| {
-                if meta.path.is_ident("name") {
-                    let value: Lit = meta.value()?.parse()?;
-                    if let Lit::Str(lit_str) = value {
-                        name = Some(lit_str.value());
+            // A bare `#[path_param]` has no arguments to parse
+            if matches!(attr.meta, syn::Meta::List(_)) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("name") {
+                        let value: syn::LitStr = meta.value()?.parse()?;
+                        name = Some(value.value());
+                        Ok(())
+                    } else {
+                        Err(meta.error("Unsupported path_param attribute"))
                     }
-                }
-                Ok(())
-            });
+                })?;
+            }
 
             return Ok(FieldAttributes {
-                field_type: FieldType::Query { name },
+                field_type: FieldType::Path { name },
+            });
+        } else if attr.path().is_ident("query") {
+            return Ok(FieldAttributes {
+                field_type: FieldType::Query(parse_query_options(attr)?),
             });
         } else if attr.path().is_ident("header") {
             let mut name = None;
+            let mut sensitive = false;
 
-            // Try to parse nested meta if the attribute has arguments
-            let _ = attr.parse_nested_meta(|meta| {
-                if meta.path.is_ident("name") {
-                    let value: Lit = meta.value()?.parse()?;
-                    if let Lit::Str(lit_str) = value {
-                        name = Some(lit_str.value());
+            // A bare `#[header]` has no arguments to parse
+            if matches!(attr.meta, syn::Meta::List(_)) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("name") {
+                        let value: syn::LitStr = meta.value()?.parse()?;
+                        name = Some(value.value());
+                    } else if meta.path.is_ident("sensitive") {
+                        sensitive = true;
+                    } else {
+                        return Err(meta.error("Unsupported header attribute"));
                     }
+                    Ok(())
+                })?;
+            }
+
+            return Ok(FieldAttributes {
+                field_type: FieldType::Header { name, sensitive },
+            });
+        } else if attr.path().is_ident("basic_auth") {
+            let mut role = None;
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("username") {
+                    role = Some(BasicAuthRole::Username);
+                } else if meta.path.is_ident("password") {
+                    role = Some(BasicAuthRole::Password);
+                } else {
+                    return Err(meta.error("Expected `username` or `password`"));
                 }
                 Ok(())
+            })?;
+            let role = role.ok_or_else(|| {
+                syn::Error::new_spanned(
+                    attr,
+                    "Expected #[basic_auth(username)] or #[basic_auth(password)]",
+                )
+            })?;
+            return Ok(FieldAttributes {
+                field_type: FieldType::BasicAuth(role),
             });
-
+        } else if attr.path().is_ident("bearer") {
             return Ok(FieldAttributes {
-                field_type: FieldType::Header { name },
+                field_type: FieldType::Bearer,
             });
         } else if attr.path().is_ident("body") {
             return Ok(FieldAttributes {
-                field_type: FieldType::Body,
+                field_type: FieldType::Body { explicit: true },
+            });
+        } else if attr.path().is_ident("file") {
+            let mut name = None;
+            // A bare `#[file]` has no arguments to parse
+            if matches!(attr.meta, syn::Meta::List(_)) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("name") {
+                        let value: syn::LitStr = meta.value()?.parse()?;
+                        name = Some(value.value());
+                        Ok(())
+                    } else {
+                        Err(meta.error("Unsupported file attribute"))
+                    }
+                })?;
+            }
+            return Ok(FieldAttributes {
+                field_type: FieldType::File { name },
             });
         }
     }
 
     // Default to body field if no attribute is specified
     Ok(FieldAttributes {
-        field_type: FieldType::Body,
+        field_type: FieldType::Body { explicit: false },
     })
 }
 
-fn generate_endpoint_impl(path: &str, path_fields: &[&syn::Ident]) -> proc_macro2::TokenStream {
-    if path_fields.is_empty() {
-        quote! {
-            fn endpoint(&self) -> String {
-                #path.to_string()
+fn parse_query_options(attr: &syn::Attribute) -> Result<QueryOptions, syn::Error> {
+    let mut options = QueryOptions::default();
+    let mut skip_if_none = false;
+
+    // A bare `#[query]` has no arguments to parse
+    if !matches!(attr.meta, syn::Meta::List(_)) {
+        return Ok(options);
+    }
+
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("name") {
+            let value: Lit = meta.value()?.parse()?;
+            if let Lit::Str(lit_str) = value {
+                options.name = Some(lit_str.value());
             }
+        } else if meta.path.is_ident("skip_if_none") {
+            skip_if_none = true;
+        } else if meta.path.is_ident("include_none") {
+            options.include_none = true;
+        } else if meta.path.is_ident("required") {
+            options.required = true;
+        } else if meta.path.is_ident("raw") {
+            options.raw = true;
+        } else if meta.path.is_ident("flatten") {
+            options.flatten = true;
+        } else if meta.path.is_ident("style") {
+            let value: syn::LitStr = meta.value()?.parse()?;
+            options.comma = match value.value().as_str() {
+                "comma" => true,
+                "repeat" => false,
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        value,
+                        "Unsupported query style, expected \"comma\" or \"repeat\"",
+                    ));
+                }
+            };
+        } else if meta.path.is_ident("with") {
+            let value: syn::LitStr = meta.value()?.parse()?;
+            let path: syn::ExprPath = value.parse()?;
+            options.with = Some(quote! { #path });
+        } else {
+            return Err(meta.error("Unsupported query attribute"));
+        }
+        Ok(())
+    })?;
+
+    if skip_if_none && options.include_none {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "`skip_if_none` and `include_none` cannot be used together",
+        ));
+    }
+
+    if options.required && options.include_none {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "`required` and `include_none` cannot be used together",
+        ));
+    }
+
+    // A flattened group supplies its own names and values
+    if options.flatten
+        && (options.name.is_some()
+            || skip_if_none
+            || options.include_none
+            || options.required
+            || options.comma
+            || options.raw
+            || options.with.is_some())
+    {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "`flatten` cannot be combined with other query options",
+        ));
+    }
+
+    if options.comma && options.with.is_some() {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "`style` and `with` cannot be used together",
+        ));
+    }
+
+    Ok(options)
+}
+
+/// Names of the `{...}` placeholders in a path template
+fn path_placeholders(path: &str) -> Vec<&str> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+        .collect()
+}
+
+fn validate_path_params(
+    path: &str,
+    path_span: proc_macro2::Span,
+    path_fields: &[PathField],
+) -> Result<(), syn::Error> {
+    let placeholders = path_placeholders(path);
+
+    for placeholder in &placeholders {
+        if !path_fields
+            .iter()
+            .any(|field| field.placeholder == *placeholder)
+        {
+            return Err(syn::Error::new(
+                path_span,
+                format!(
+                    "Path placeholder `{{{}}}` has no matching #[path_param] field",
+                    placeholder
+                ),
+            ));
+        }
+    }
+
+    for field in path_fields {
+        if !placeholders.contains(&field.placeholder.as_str()) {
+            return Err(syn::Error::new_spanned(
+                field.ident,
+                format!(
+                    "#[path_param] field `{}` has no `{{{}}}` placeholder in the path",
+                    field.ident, field.placeholder
+                ),
+            ));
         }
+    }
+
+    Ok(())
+}
+
+fn generate_endpoint_impl(path: &str, path_fields: &[PathField]) -> proc_macro2::TokenStream {
+    let access = |field: &syn::Ident| quote! { self.#field };
+    let endpoint_code = generate_endpoint_expr(path, path_fields, false, access);
+
+    // Path params that would change the route are rejected when building
+    let try_endpoint_impl = if path_fields.is_empty() {
+        quote! {}
     } else {
-        let mut endpoint_code = quote! {
-            let mut endpoint = #path.to_string();
+        let try_endpoint_code = generate_endpoint_expr(path, path_fields, true, access);
+        quote! {
+            fn try_endpoint(
+                &self,
+            ) -> std::result::Result<String, ::reqwest_builder::ReqwestBuilderError> {
+                Ok({ #try_endpoint_code })
+            }
+        }
+    };
+
+    quote! {
+        fn endpoint(&self) -> String {
+            #endpoint_code
+        }
+
+        #try_endpoint_impl
+    }
+}
+
+/// Build the endpoint string, reading each path field through `access`
+///
+/// With `fallible`, values are encoded with `try_encode_path_param` and the
+/// expression uses `?`.
+fn generate_endpoint_expr(
+    path: &str,
+    path_fields: &[PathField],
+    fallible: bool,
+    access: impl Fn(&syn::Ident) -> proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    if path_fields.is_empty() {
+        return quote! { #path.to_string() };
+    }
+
+    let mut endpoint_code = quote! {
+        let mut endpoint = #path.to_string();
+    };
+
+    for path_field in path_fields {
+        let value = access(path_field.ident);
+        let placeholder = format!("{{{}}}", path_field.placeholder);
+
+        let encoded = if fallible {
+            quote! { reqwest_builder::try_encode_path_param(&#value.to_string())? }
+        } else {
+            quote! { reqwest_builder::encode_path_param(&#value.to_string()) }
         };
+        endpoint_code.extend(quote! {
+            endpoint = endpoint.replace(#placeholder, &#encoded);
+        });
+    }
 
-        for field in path_fields {
-            let field_name_str = field.to_string();
-            let placeholder = format!("{{{}}}", field_name_str);
+    endpoint_code.extend(quote! {
+        endpoint
+    });
 
-            endpoint_code.extend(quote! {
-                endpoint = endpoint.replace(#placeholder, &self.#field.to_string());
-            });
+    endpoint_code
+}
+
+/// Implement the trait for an enum whose variants each carry `#[request(...)]`
+///
+/// `method()`, `endpoint()` and `body()` match on the variant. Variant fields
+/// may be `#[path_param]` or body fields; the body is the enum's own serde
+/// representation (externally tagged unless serde attributes say otherwise)
+/// with the path fields left out.
+fn impl_enum_into_reqwest_builder(
+    input: &DeriveInput,
+    data_enum: &syn::DataEnum,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let name = &input.ident;
+
+    if data_enum.variants.is_empty() {
+        return Err(syn::Error::new_spanned(
+            input,
+            "Enums need at least one variant",
+        ));
+    }
+
+    let mut method_arms = Vec::new();
+    let mut endpoint_arms = Vec::new();
+    let mut try_endpoint_arms = Vec::new();
+    let mut body_arms = Vec::new();
+    let mut view_variants = Vec::new();
+
+    for variant in &data_enum.variants {
+        let variant_name = &variant.ident;
+
+        if !variant
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("request"))
+        {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "Each enum variant needs a #[request(method = \"...\", path = \"...\")] attribute",
+            ));
+        }
+        let container_attrs = parse_container_attributes(&variant.attrs)?;
+        if container_attrs.rename_all.is_some() {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "`rename_all` is not supported on enum variants",
+            ));
+        }
+        if !container_attrs.static_headers.is_empty() {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "`header` and `header_const` are not supported on enum variants",
+            ));
+        }
+        if container_attrs.form_arrays.is_some() {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "`form_arrays` is not supported on enum variants",
+            ));
+        }
+        if container_attrs.serde_query.is_some() {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "`query = \"serde\"` and `query = \"qs\"` are not supported on enum variants",
+            ));
+        }
+        if !matches!(
+            container_attrs.body_kind,
+            BodyKind::Json | BodyKind::Form | BodyKind::None
+        ) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "Enum variants support only `json`, `form` and `none` bodies",
+            ));
         }
 
-        endpoint_code.extend(quote! {
-            endpoint
+        let mut path_fields = Vec::new();
+        let mut view_fields = Vec::new();
+        match &variant.fields {
+            Fields::Named(fields) => {
+                for field in &fields.named {
+                    let field_name = field.ident.as_ref().unwrap();
+                    let ty = &field.ty;
+                    match parse_field_attributes(&field.attrs)?.field_type {
+                        FieldType::Path { name } => {
+                            path_fields.push(PathField {
+                                ident: field_name,
+                                placeholder: name.unwrap_or_else(|| field_name.unraw().to_string()),
+                            });
+                            view_fields.push(quote! {
+                                #[serde(skip)]
+                                #field_name: #ty
+                            });
+                        }
+                        FieldType::Body { .. } => {
+                            let attrs = serde_attributes(&field.attrs);
+                            view_fields.push(quote! {
+                                #(#attrs)*
+                                #field_name: #ty
+                            });
+                        }
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                field,
+                                "Only #[path_param] and body fields are supported on enum variants",
+                            ));
+                        }
+                    }
+                }
+            }
+            Fields::Unit => {}
+            Fields::Unnamed(_) => {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    "Only named or unit enum variants are supported",
+                ));
+            }
+        }
+
+        validate_path_params(
+            &container_attrs.path,
+            container_attrs.path_span,
+            &path_fields,
+        )?;
+
+        let method = container_attrs.method;
+        method_arms.push(quote! {
+            Self::#variant_name { .. } => #method
         });
 
-        quote! {
-            fn endpoint(&self) -> String {
+        let bindings: Vec<_> = path_fields.iter().map(|field| field.ident).collect();
+        let endpoint_code = generate_endpoint_expr(
+            &container_attrs.path,
+            &path_fields,
+            false,
+            |field| quote! { #field },
+        );
+        endpoint_arms.push(quote! {
+            Self::#variant_name { #(#bindings,)* .. } => {
                 #endpoint_code
             }
+        });
+        let try_endpoint_code = generate_endpoint_expr(
+            &container_attrs.path,
+            &path_fields,
+            true,
+            |field| quote! { #field },
+        );
+        try_endpoint_arms.push(quote! {
+            Self::#variant_name { #(#bindings,)* .. } => {
+                #try_endpoint_code
+            }
+        });
+
+        let body_type = container_attrs.body_kind.to_tokens();
+        body_arms.push(quote! {
+            Self::#variant_name { .. } => #body_type
+        });
+
+        let variant_attrs = serde_attributes(&variant.attrs);
+        view_variants.push(match &variant.fields {
+            Fields::Unit => quote! {
+                #(#variant_attrs)*
+                #variant_name
+            },
+            _ => quote! {
+                #(#variant_attrs)*
+                #variant_name { #(#view_fields),* }
+            },
+        });
+    }
+
+    // Same serde `remote` trick as struct bodies, with path fields skipped
+    let view_name = quote::format_ident!("__{}Body", name);
+    let remote = name.to_string();
+    let container_serde_attrs = serde_attributes(&input.attrs);
+
+    let (headers_type, headers_impl, headers_struct_name) = generate_headers_impl(name, &[], &[]);
+
+    Ok(quote! {
+        #headers_type
+
+        impl ::reqwest_builder::IntoReqwestBuilder for #name {
+            type Headers = #headers_struct_name;
+
+            fn method(&self) -> http::Method {
+                match self {
+                    #(#method_arms),*
+                }
+            }
+
+            fn endpoint(&self) -> String {
+                match self {
+                    #(#endpoint_arms),*
+                }
+            }
+
+            fn try_endpoint(
+                &self,
+            ) -> std::result::Result<String, ::reqwest_builder::ReqwestBuilderError> {
+                Ok(match self {
+                    #(#try_endpoint_arms),*
+                })
+            }
+
+            #headers_impl
+
+            fn body(&self) -> ::reqwest_builder::RequestBody {
+                match self {
+                    #(#body_arms),*
+                }
+            }
+
+            fn serialize_body(
+                &self,
+            ) -> std::result::Result<serde_json::Value, ::reqwest_builder::ReqwestBuilderError> {
+                #[derive(serde::Serialize)]
+                #[serde(remote = #remote)]
+                #(#container_serde_attrs)*
+                #[allow(dead_code)]
+                enum #view_name {
+                    #(#view_variants),*
+                }
+
+                Ok(#view_name::serialize(self, serde_json::value::Serializer)?)
+            }
+        }
+    })
+}
+
+/// A newtype struct like `struct CreateUser(UserPayload)` sends its field as the body
+fn impl_newtype_into_reqwest_builder(
+    input: &DeriveInput,
+    fields: &syn::FieldsUnnamed,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let name = &input.ident;
+
+    let field = match fields.unnamed.iter().collect::<Vec<_>>().as_slice() {
+        [field] => *field,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                fields,
+                "Tuple structs need exactly one field, which is sent as the body",
+            ));
         }
+    };
+    if !matches!(
+        parse_field_attributes(&field.attrs)?.field_type,
+        FieldType::Body { .. }
+    ) {
+        return Err(syn::Error::new_spanned(
+            field,
+            "The field of a newtype struct is always the body",
+        ));
     }
+
+    let container_attrs = parse_container_attributes(&input.attrs)?;
+    if container_attrs.rename_all.is_some() {
+        return Err(syn::Error::new_spanned(
+            input,
+            "`rename_all` is not supported on newtype structs",
+        ));
+    }
+    if !matches!(container_attrs.body_kind, BodyKind::Json | BodyKind::Form) {
+        return Err(syn::Error::new_spanned(
+            input,
+            "Newtype structs support only `json` and `form` bodies",
+        ));
+    }
+    if container_attrs.serde_query.is_some() {
+        return Err(syn::Error::new_spanned(
+            input,
+            "`query = \"serde\"` and `query = \"qs\"` are not supported on newtype structs",
+        ));
+    }
+
+    // A newtype has no path fields, so the path can't have placeholders
+    validate_path_params(&container_attrs.path, container_attrs.path_span, &[])?;
+    let endpoint_impl = generate_endpoint_impl(&container_attrs.path, &[]);
+
+    let (headers_type, headers_impl, headers_struct_name) =
+        generate_headers_impl(name, &[], &container_attrs.static_headers);
+
+    let method = &container_attrs.method;
+    let body_type = container_attrs.body_kind.to_tokens();
+    let form_array_style_impl = form_array_style_impl(container_attrs.form_arrays.as_ref());
+
+    Ok(quote! {
+        #headers_type
+
+        impl ::reqwest_builder::IntoReqwestBuilder for #name {
+            type Headers = #headers_struct_name;
+
+            fn method(&self) -> http::Method {
+                #method
+            }
+
+            #endpoint_impl
+
+            #headers_impl
+
+            fn body(&self) -> ::reqwest_builder::RequestBody {
+                #body_type
+            }
+            #form_array_style_impl
+
+            fn serialize_body(
+                &self,
+            ) -> std::result::Result<serde_json::Value, ::reqwest_builder::ReqwestBuilderError> {
+                Ok(serde_json::to_value(&self.0)?)
+            }
+        }
+    })
 }
 
-fn generate_query_params_impl(query_fields: &[(&syn::Ident, String)]) -> proc_macro2::TokenStream {
-    if query_fields.is_empty() {
+fn form_array_style_impl(
+    form_arrays: Option<&proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    match form_arrays {
+        Some(style) => quote! {
+            fn form_array_style(&self) -> ::reqwest_builder::FormArrayStyle {
+                #style
+            }
+        },
+        None => quote! {},
+    }
+}
+
+fn generate_raw_body_impl(
+    input: &DeriveInput,
+    body_kind: &BodyKind,
+    body_fields: &[&syn::Ident],
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    match body_kind {
+        BodyKind::Text => {
+            let field = single_body_field(input, "text", body_fields)?;
+            Ok(quote! {
+                fn text_body(&self) -> Option<String> {
+                    Some(self.#field.to_string())
+                }
+            })
+        }
+        BodyKind::Bytes => {
+            let field = single_body_field(input, "bytes", body_fields)?;
+            Ok(quote! {
+                fn bytes_body(&self) -> Option<Vec<u8>> {
+                    Some(<_ as AsRef<[u8]>>::as_ref(&self.#field).to_vec())
+                }
+            })
+        }
+        BodyKind::Ndjson => {
+            let field = single_body_field(input, "ndjson", body_fields)?;
+            Ok(quote! {
+                fn ndjson_records(&self) -> Option<Vec<serde_json::Value>> {
+                    reqwest_builder::ndjson_records_helper(&self.#field)
+                }
+            })
+        }
+        _ => Ok(quote! {}),
+    }
+}
+
+fn generate_multipart_impl(
+    body_kind: &BodyKind,
+    text_fields: &[MultipartField],
+    file_fields: &[MultipartField],
+    max_multipart_bytes: Option<u64>,
+) -> proc_macro2::TokenStream {
+    if !matches!(body_kind, BodyKind::Multipart) {
+        return quote! {};
+    }
+
+    let file_refs: Vec<_> = file_fields
+        .iter()
+        .map(|MultipartField { field, .. }| {
+            let ident = field.ident.as_ref().unwrap();
+            if is_option_type(&field.ty) {
+                quote! { files.extend(self.#ident.as_ref()); }
+            } else {
+                quote! { files.push(&self.#ident); }
+            }
+        })
+        .collect();
+
+    let max_bytes_impl = max_multipart_bytes.map(|limit| {
         quote! {
-            fn query_params(&self) -> Option<std::collections::HashMap<String, String>> {
+            fn max_multipart_bytes(&self) -> Option<u64> {
+                Some(#limit)
+            }
+        }
+    });
+
+    let text_parts: Vec<_> = text_fields
+        .iter()
+        .map(|MultipartField { field, part_name }| {
+            let ident = field.ident.as_ref().unwrap();
+            quote! {
+                form = reqwest_builder::multipart_text_helper(form, #part_name, &self.#ident);
+            }
+        })
+        .collect();
+
+    let file_parts: Vec<_> = file_fields
+        .iter()
+        .map(|MultipartField { field, part_name }| {
+            let ident = field.ident.as_ref().unwrap();
+            if is_option_type(&field.ty) {
+                // `None` files are left out of the form
+                quote! {
+                    if let Some(file) = &self.#ident {
+                        form = form.part(#part_name, file.try_to_multipart_part()?);
+                    }
+                }
+            } else {
+                quote! {
+                    form = form.part(#part_name, self.#ident.try_to_multipart_part()?);
+                }
+            }
+        })
+        .collect();
+
+    quote! {
+        fn try_create_multipart_form(
+            &self,
+        ) -> std::result::Result<Option<reqwest::multipart::Form>, ::reqwest_builder::ReqwestBuilderError>
+        {
+            let mut form = reqwest::multipart::Form::new();
+            #(#text_parts)*
+            #(#file_parts)*
+            Ok(Some(form))
+        }
+
+        fn create_multipart_form(&self) -> Option<reqwest::multipart::Form> {
+            self.try_create_multipart_form().ok().flatten()
+        }
+
+        #[allow(clippy::vec_init_then_push)]
+        fn multipart_files(&self) -> Vec<&::reqwest_builder::FileUpload> {
+            let mut files = Vec::new();
+            #(#file_refs)*
+            files
+        }
+
+        #max_bytes_impl
+    }
+}
+
+fn single_body_field<'a>(
+    input: &DeriveInput,
+    body_name: &str,
+    body_fields: &[&'a syn::Ident],
+) -> Result<&'a syn::Ident, syn::Error> {
+    match body_fields {
+        [field] => Ok(field),
+        _ => Err(syn::Error::new_spanned(
+            input,
+            format!("body = \"{}\" requires exactly one body field", body_name),
+        )),
+    }
+}
+
+fn generate_query_params_impl(query_fields: &[QueryField]) -> proc_macro2::TokenStream {
+    let (raw_fields, encoded_fields): (Vec<_>, Vec<_>) =
+        query_fields.iter().partition(|query_field| query_field.raw);
+
+    let query_params_impl = if encoded_fields.is_empty() {
+        quote! {
+            fn query_params(&self) -> Option<Vec<(String, String)>> {
                 None
             }
         }
     } else {
-        let param_insertions: Vec<_> = query_fields
+        let param_insertions = encoded_fields
             .iter()
-            .map(|(field, param_name)| {
-                quote! {
-                    // Handle query parameters - this works for both Option and non-Option types
-                    let field_ref = &self.#field;
-                    reqwest_builder::query_param_helper(field_ref, #param_name, &mut params);
+            .map(|field| query_param_insertion(field));
+        quote! {
+            fn query_params(&self) -> Option<Vec<(String, String)>> {
+                use reqwest_builder::{QueryParamViaDisplay as _, QueryParamViaValue as _};
+                let mut params = Vec::new();
+                #(#param_insertions)*
+                if params.is_empty() {
+                    None
+                } else {
+                    Some(params)
                 }
-            })
-            .collect();
+            }
+        }
+    };
 
+    // Pre-encoded fields keep the trait's default otherwise
+    let raw_query_params_impl = if raw_fields.is_empty() {
+        quote! {}
+    } else {
+        let param_insertions = raw_fields.iter().map(|field| query_param_insertion(field));
         quote! {
-            fn query_params(&self) -> Option<std::collections::HashMap<String, String>> {
-                let mut params = std::collections::HashMap::new();
+            fn raw_query_params(&self) -> Option<Vec<(String, String)>> {
+                use reqwest_builder::{QueryParamViaDisplay as _, QueryParamViaValue as _};
+                let mut params = Vec::new();
                 #(#param_insertions)*
                 if params.is_empty() {
                     None
@@ -352,12 +1694,255 @@ fn generate_query_params_impl(query_fields: &[(&syn::Ident, String)]) -> proc_ma
                 }
             }
         }
+    };
+
+    // Required parameters are checked when the request is built
+    let required_names: Vec<_> = query_fields
+        .iter()
+        .filter(|query_field| query_field.required)
+        .map(|query_field| &query_field.param_name)
+        .collect();
+    let try_query_params_impl = if required_names.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            fn try_query_params(
+                &self,
+            ) -> std::result::Result<Option<Vec<(String, String)>>, ::reqwest_builder::ReqwestBuilderError>
+            {
+                let params = self.query_params();
+                let raw_params = self.raw_query_params();
+                for name in [#(#required_names),*] {
+                    let present = params
+                        .iter()
+                        .chain(raw_params.iter())
+                        .flatten()
+                        .any(|(key, value)| key == name && !value.is_empty());
+                    if !present {
+                        return Err(::reqwest_builder::ReqwestBuilderError::QueryError {
+                            key: name.to_string(),
+                            reason: "Required query parameter is missing or empty".to_string(),
+                        });
+                    }
+                }
+                Ok(params)
+            }
+        }
+    };
+
+    quote! {
+        #query_params_impl
+        #raw_query_params_impl
+        #try_query_params_impl
+    }
+}
+
+/// Statements pushing one query field's values onto `params`
+fn query_param_insertion(query_field: &QueryField) -> proc_macro2::TokenStream {
+    let field = query_field.ident;
+    let param_name = &query_field.param_name;
+    if query_field.flatten {
+        return quote! {
+            params.extend(reqwest_builder::IntoQueryParams::to_query_params(&self.#field));
+        };
+    }
+    let insertion = match &query_field.with {
+        // A custom formatter always produces exactly one value
+        Some(with) => quote! {
+            params.push((#param_name.to_string(), #with(&self.#field)));
+        },
+        None => {
+            let value = if query_field.comma {
+                quote! { &reqwest_builder::CommaSeparated(&field_ref[..]) }
+            } else {
+                quote! { field_ref }
+            };
+            if query_field.unwrap_option {
+                quote! {
+                    if let Some(field_ref) = &self.#field {
+                        (&reqwest_builder::QueryParamDispatch(#value))
+                            .add_query_param(#param_name, &mut params);
+                    }
+                }
+            } else {
+                quote! {
+                    // Handle query parameters - this works for Option, Vec and any Display type
+                    let field_ref = &self.#field;
+                    (&reqwest_builder::QueryParamDispatch(#value))
+                        .add_query_param(#param_name, &mut params);
+                }
+            }
+        }
+    };
+    if query_field.include_none {
+        quote! {
+            let params_before = params.len();
+            #insertion
+            if params.len() == params_before {
+                params.push((#param_name.to_string(), String::new()));
+            }
+        }
+    } else {
+        insertion
+    }
+}
+
+/// Check whether a field type is an `Option<T>`
+fn is_option_type(ty: &syn::Type) -> bool {
+    is_type_named(ty, "Option")
+}
+
+/// Check that a method name is a valid HTTP token (RFC 9110 `tchar`s)
+fn is_http_token(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&byte))
+}
+
+fn is_type_named(ty: &syn::Type, name: &str) -> bool {
+    if let syn::Type::Path(type_path) = ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            return segment.ident == name;
+        }
+    }
+    false
+}
+
+/// Serialize only the body fields by deriving a serde "remote" view of the struct
+///
+/// The view mirrors the request's body fields with their original types and
+/// `serde` attributes, so renames and skips behave exactly as on the request,
+/// while path, query, header and file fields are left out.
+fn generate_serialize_body_impl(
+    input: &DeriveInput,
+    body_fields: &[&syn::Field],
+) -> proc_macro2::TokenStream {
+    let view_name = quote::format_ident!("__{}Body", input.ident);
+    let view_struct = generate_serde_view(input, &view_name, body_fields);
+
+    quote! {
+        fn serialize_body(
+            &self,
+        ) -> std::result::Result<serde_json::Value, ::reqwest_builder::ReqwestBuilderError> {
+            #view_struct
+
+            Ok(#view_name::serialize(self, serde_json::value::Serializer)?)
+        }
+    }
+}
+
+/// `try_query_params` for `query = "serde"` or `query = "qs"`, serializing
+/// the given fields
+fn generate_serde_query_impl(
+    input: &DeriveInput,
+    style: &str,
+    query_fields: &[&syn::Field],
+) -> proc_macro2::TokenStream {
+    let name = &input.ident;
+    let serialize = if style == "qs" {
+        quote! { ::reqwest_builder::serialize_to_nested_query_params }
+    } else {
+        quote! { ::reqwest_builder::serialize_to_query_params }
+    };
+    let view_name = quote::format_ident!("__{}Query", name);
+    let view_struct = generate_serde_view(input, &view_name, query_fields);
+
+    quote! {
+        fn try_query_params(
+            &self,
+        ) -> std::result::Result<Option<Vec<(String, String)>>, ::reqwest_builder::ReqwestBuilderError>
+        {
+            #view_struct
+
+            // The remote view only provides a `serialize` function, not the trait
+            struct QueryView<'a>(&'a #name);
+
+            impl serde::Serialize for QueryView<'_> {
+                fn serialize<S: serde::Serializer>(
+                    &self,
+                    serializer: S,
+                ) -> std::result::Result<S::Ok, S::Error> {
+                    #view_name::serialize(self.0, serializer)
+                }
+            }
+
+            let params = #serialize(&QueryView(self))?;
+            Ok(if params.is_empty() { None } else { Some(params) })
+        }
+
+        fn query_params(&self) -> Option<Vec<(String, String)>> {
+            self.try_query_params().ok().flatten()
+        }
     }
 }
 
+/// A serde `remote` view of the struct limited to `fields`, keeping their
+/// serde attributes and the container's
+fn generate_serde_view(
+    input: &DeriveInput,
+    view_name: &syn::Ident,
+    fields: &[&syn::Field],
+) -> proc_macro2::TokenStream {
+    let remote = input.ident.to_string();
+
+    let container_attrs = serde_attributes(&input.attrs);
+    let view_fields: Vec<_> = fields
+        .iter()
+        .map(|field| {
+            let attrs = serde_attributes(&field.attrs);
+            let ident = &field.ident;
+            let ty = &field.ty;
+            quote! {
+                #(#attrs)*
+                #ident: #ty
+            }
+        })
+        .collect();
+
+    quote! {
+        #[derive(serde::Serialize)]
+        #[serde(remote = #remote)]
+        #(#container_attrs)*
+        #[allow(dead_code)]
+        struct #view_name {
+            #(#view_fields),*
+        }
+    }
+}
+
+/// Reject `field` if an earlier field already sets the Authorization header
+fn check_single_authorization(
+    field: &syn::Field,
+    header_fields: &[HeaderField],
+) -> Result<(), syn::Error> {
+    if header_fields.iter().any(|header_field| {
+        header_field
+            .header_name
+            .eq_ignore_ascii_case("Authorization")
+    }) {
+        return Err(syn::Error::new_spanned(
+            field,
+            "Only one Authorization source is allowed: #[bearer], #[basic_auth] or \
+             #[header(name = \"Authorization\")]",
+        ));
+    }
+    Ok(())
+}
+
+/// The `#[serde(...)]` attributes among `attrs`
+fn serde_attributes(attrs: &[syn::Attribute]) -> Vec<syn::Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .cloned()
+        .collect()
+}
+
 fn generate_headers_impl(
     struct_name: &syn::Ident,
-    header_fields: &[(&syn::Ident, String)],
+    header_fields: &[HeaderField],
+    static_headers: &[(String, proc_macro2::TokenStream)],
 ) -> (
     proc_macro2::TokenStream,
     proc_macro2::TokenStream,
@@ -365,7 +1950,7 @@ fn generate_headers_impl(
 ) {
     let headers_struct_name = quote::format_ident!("{}Headers", struct_name);
 
-    if header_fields.is_empty() {
+    if header_fields.is_empty() && static_headers.is_empty() {
         let headers_type = quote! {
             #[derive(serde::Serialize, Clone)]
             pub struct #headers_struct_name;
@@ -380,15 +1965,55 @@ fn generate_headers_impl(
         return (headers_type, headers_impl, quote! { #headers_struct_name });
     }
 
-    let header_struct_fields: Vec<_> = header_fields
+    // A field with the same name wins; an `Option` field falls back to the constant
+    let field_header = |name: &str| {
+        header_fields
+            .iter()
+            .find(|header_field| header_field.header_name.eq_ignore_ascii_case(name))
+    };
+    let static_header_fields: Vec<_> = static_headers
+        .iter()
+        .enumerate()
+        .filter(|(_, (name, _))| field_header(name).is_none())
+        .map(|(index, (name, value))| {
+            (
+                quote::format_ident!("__static_header_{}", index),
+                name,
+                value,
+            )
+        })
+        .collect();
+
+    let header_struct_fields: Vec<_> = static_header_fields
         .iter()
-        .map(|(field, header_name)| {
-            let field_type = quote! { String };
+        .map(|(field, header_name, _)| {
             quote! {
                 #[serde(rename = #header_name)]
-                pub #field: #field_type
+                pub #field: String
             }
         })
+        .chain(header_fields.iter().map(|header_field| {
+            let field = &header_field.ident;
+            let header_name = &header_field.header_name;
+            if header_field.optional {
+                // `None` headers are left out of the serialized header map
+                quote! {
+                    #[serde(rename = #header_name, skip_serializing_if = "Option::is_none")]
+                    pub #field: Option<String>
+                }
+            } else if header_field.multiple {
+                // Each element is appended as a separate value
+                quote! {
+                    #[serde(rename = #header_name)]
+                    pub #field: Vec<String>
+                }
+            } else {
+                quote! {
+                    #[serde(rename = #header_name)]
+                    pub #field: String
+                }
+            }
+        }))
         .collect();
 
     let headers_type = quote! {
@@ -398,13 +2023,25 @@ fn generate_headers_impl(
         }
     };
 
-    let header_assignments: Vec<_> = header_fields
+    let header_assignments: Vec<_> = static_header_fields
         .iter()
-        .map(|(field, _)| {
-            quote! {
-                #field: self.#field.to_string()
+        .map(|(field, _, value)| quote! { #field: #value.to_string() })
+        .chain(header_fields.iter().map(|header_field| {
+            let field = &header_field.ident;
+            let value = &header_field.value;
+            let fallback = static_headers
+                .iter()
+                .find(|(name, _)| header_field.header_name.eq_ignore_ascii_case(name))
+                .filter(|_| header_field.optional);
+            match fallback {
+                Some((_, default)) => quote! {
+                    #field: (#value).or_else(|| Some(#default.to_string()))
+                },
+                None => quote! {
+                    #field: #value
+                },
             }
-        })
+        }))
         .collect();
 
     let headers_impl = quote! {
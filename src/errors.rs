@@ -1,8 +1,38 @@
+use std::sync::Arc;
+
+/// Underlying error preserved for `std::error::Error::source`
+///
+/// Shared behind an `Arc` so `ReqwestBuilderError` stays `Clone`; two sources
+/// compare equal when their messages match.
+#[derive(Debug, Clone)]
+pub struct ErrorSource(Arc<dyn std::error::Error + Send + Sync>);
+
+impl ErrorSource {
+    /// Wrap an underlying error
+    pub fn new<E: std::error::Error + Send + Sync + 'static>(err: E) -> Self {
+        Self(Arc::new(err))
+    }
+
+    /// Access the underlying error
+    pub fn inner(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+        self.0.as_ref()
+    }
+}
+
+impl PartialEq for ErrorSource {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_string() == other.0.to_string()
+    }
+}
+
 /// Custom error types for the reqwest-builder library
 #[derive(Debug, Clone, PartialEq)]
 pub enum ReqwestBuilderError {
     /// Error serializing data to JSON
-    SerializationError(String),
+    SerializationError {
+        message: String,
+        source: Option<ErrorSource>,
+    },
     /// Error with header name or value
     HeaderError {
         key: String,
@@ -11,38 +41,111 @@ pub enum ReqwestBuilderError {
     },
     /// Error constructing URL
     UrlError(String),
+    /// Invalid query parameter, such as an empty key
+    QueryError { key: String, reason: String },
     /// File I/O error
-    IoError(String),
+    IoError {
+        message: String,
+        source: Option<ErrorSource>,
+    },
     /// Invalid request configuration
     InvalidRequest(String),
+    /// Error building a multipart form, such as a part with an invalid MIME type
+    MultipartError(String),
+    /// Error sending the request
+    RequestError(String),
+    /// The request or response didn't finish within the timeout
+    Timeout(String),
+    /// Non-success response status
+    ResponseError { status: u16, body: String },
+    /// Non-success response whose body parsed as the API's error type,
+    /// carried in its `Debug` form
+    ApiError { status: u16, error: String },
 }
 
 impl std::fmt::Display for ReqwestBuilderError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            ReqwestBuilderError::SerializationError(msg) => {
-                write!(f, "Serialization error: {}", msg)
+            ReqwestBuilderError::SerializationError { message, .. } => {
+                write!(f, "Serialization error: {}", message)
             }
             ReqwestBuilderError::HeaderError { key, value, source } => {
                 write!(f, "Header error for '{}': '{}' - {}", key, value, source)
             }
             ReqwestBuilderError::UrlError(msg) => write!(f, "URL error: {}", msg),
-            ReqwestBuilderError::IoError(msg) => write!(f, "I/O error: {}", msg),
+            ReqwestBuilderError::QueryError { key, reason } => {
+                write!(f, "Query error for '{}': {}", key, reason)
+            }
+            ReqwestBuilderError::IoError { message, .. } => write!(f, "I/O error: {}", message),
             ReqwestBuilderError::InvalidRequest(msg) => write!(f, "Invalid request: {}", msg),
+            ReqwestBuilderError::MultipartError(msg) => write!(f, "Multipart error: {}", msg),
+            ReqwestBuilderError::RequestError(msg) => write!(f, "Request error: {}", msg),
+            ReqwestBuilderError::Timeout(msg) => write!(f, "Request timed out: {}", msg),
+            ReqwestBuilderError::ResponseError { status, body } => {
+                write!(f, "Response error: status {} - {}", status, body)
+            }
+            ReqwestBuilderError::ApiError { status, error } => {
+                write!(f, "API error: status {} - {}", status, error)
+            }
         }
     }
 }
 
-impl std::error::Error for ReqwestBuilderError {}
+impl std::error::Error for ReqwestBuilderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReqwestBuilderError::SerializationError {
+                source: Some(source),
+                ..
+            }
+            | ReqwestBuilderError::IoError {
+                source: Some(source),
+                ..
+            } => Some(source.inner()),
+            _ => None,
+        }
+    }
+}
 
 impl From<std::io::Error> for ReqwestBuilderError {
     fn from(err: std::io::Error) -> Self {
-        ReqwestBuilderError::IoError(err.to_string())
+        ReqwestBuilderError::IoError {
+            message: err.to_string(),
+            source: Some(ErrorSource::new(err)),
+        }
     }
 }
 
 impl From<serde_json::Error> for ReqwestBuilderError {
     fn from(err: serde_json::Error) -> Self {
-        ReqwestBuilderError::SerializationError(err.to_string())
+        ReqwestBuilderError::SerializationError {
+            message: err.to_string(),
+            source: Some(ErrorSource::new(err)),
+        }
+    }
+}
+
+impl From<url::ParseError> for ReqwestBuilderError {
+    fn from(err: url::ParseError) -> Self {
+        ReqwestBuilderError::UrlError(err.to_string())
+    }
+}
+
+impl From<reqwest::Error> for ReqwestBuilderError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            ReqwestBuilderError::Timeout(err.to_string())
+        } else {
+            ReqwestBuilderError::RequestError(err.to_string())
+        }
+    }
+}
+
+impl From<reqwest_middleware::Error> for ReqwestBuilderError {
+    fn from(err: reqwest_middleware::Error) -> Self {
+        match err {
+            reqwest_middleware::Error::Reqwest(err) => err.into(),
+            err => ReqwestBuilderError::RequestError(err.to_string()),
+        }
     }
 }
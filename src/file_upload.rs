@@ -1,13 +1,44 @@
 use crate::errors::ReqwestBuilderError;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// Source of a file upload's content
+///
+/// More sources may be added, so matches need a wildcard arm.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum FileContent {
+    /// Content held in memory
+    Bytes(Vec<u8>),
+    /// Content read from disk when the request is sent
+    ///
+    /// Streamed with the `stream` feature; otherwise the file is read into
+    /// memory when its multipart part is built.
+    Path { path: PathBuf, len: u64 },
+}
+
+impl FileContent {
+    /// The content, if it is held in memory
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            FileContent::Bytes(bytes) => Some(bytes),
+            FileContent::Path { .. } => None,
+        }
+    }
+}
+
+impl Default for FileContent {
+    fn default() -> Self {
+        FileContent::Bytes(Vec::new())
+    }
+}
 
 /// File data for upload
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct FileUpload {
     pub filename: String,
     #[serde(skip)] // Don't serialize file content
-    pub content: Vec<u8>,
+    pub content: FileContent,
     #[serde(skip)] // Don't serialize mime type
     pub mime_type: Option<String>,
 }
@@ -17,20 +48,69 @@ impl FileUpload {
     pub fn from_path<P: AsRef<Path>>(path: P) -> std::result::Result<Self, ReqwestBuilderError> {
         let path = path.as_ref();
         let content = std::fs::read(path)?;
-        let filename = path
-            .file_name()
-            .and_then(|name| name.to_str())
-            .unwrap_or("file")
-            .to_string();
 
-        let mime_type = mime_guess::from_path(path)
-            .first()
-            .map(|mime| mime.to_string());
+        Ok(Self {
+            filename: filename_from_path(path),
+            content: FileContent::Bytes(content),
+            mime_type: mime_type_from_path(path),
+        })
+    }
+
+    /// Create a new file upload from file path, rejecting files over `max_bytes`
+    ///
+    /// The size is checked from the file metadata before any content is read.
+    pub fn from_path_with_limit<P: AsRef<Path>>(
+        path: P,
+        max_bytes: u64,
+    ) -> std::result::Result<Self, ReqwestBuilderError> {
+        let path = path.as_ref();
+        let len = std::fs::metadata(path)?.len();
+
+        if len > max_bytes {
+            return Err(ReqwestBuilderError::InvalidRequest(format!(
+                "File '{}' is {} bytes, exceeding the limit of {} bytes",
+                path.display(),
+                len,
+                max_bytes
+            )));
+        }
+
+        Self::from_path(path)
+    }
+
+    /// Create a new file upload from file path without blocking the async runtime
+    #[cfg(feature = "tokio")]
+    pub async fn from_path_async<P: AsRef<Path>>(
+        path: P,
+    ) -> std::result::Result<Self, ReqwestBuilderError> {
+        let path = path.as_ref();
+        let content = tokio::fs::read(path).await?;
 
         Ok(Self {
-            filename,
-            content,
-            mime_type,
+            filename: filename_from_path(path),
+            content: FileContent::Bytes(content),
+            mime_type: mime_type_from_path(path),
+        })
+    }
+
+    /// Create a new file upload that streams its content from disk
+    ///
+    /// Only the file metadata is read here; the file is opened and streamed
+    /// when the multipart part is sent, so large files are never buffered.
+    #[cfg(feature = "stream")]
+    pub fn from_path_streaming<P: AsRef<Path>>(
+        path: P,
+    ) -> std::result::Result<Self, ReqwestBuilderError> {
+        let path = path.as_ref();
+        let len = std::fs::metadata(path)?.len();
+
+        Ok(Self {
+            filename: filename_from_path(path),
+            content: FileContent::Path {
+                path: path.to_path_buf(),
+                len,
+            },
+            mime_type: mime_type_from_path(path),
         })
     }
 
@@ -38,8 +118,134 @@ impl FileUpload {
     pub fn from_bytes(filename: String, content: Vec<u8>, mime_type: Option<String>) -> Self {
         Self {
             filename,
-            content,
+            content: FileContent::Bytes(content),
             mime_type,
         }
     }
+
+    /// Create a new file upload by reading `reader` to completion
+    ///
+    /// For content that is produced in memory or by another source, e.g. a
+    /// `Cursor` or a decompressor, rather than read from a file path.
+    pub fn from_reader<R: std::io::Read>(
+        filename: String,
+        mut reader: R,
+        mime_type: Option<String>,
+    ) -> std::result::Result<Self, ReqwestBuilderError> {
+        let mut content = Vec::new();
+        reader.read_to_end(&mut content)?;
+
+        Ok(Self::from_bytes(filename, content, mime_type))
+    }
+
+    /// Override the guessed MIME type
+    ///
+    /// Returns `ReqwestBuilderError::InvalidRequest` if `mime_type` isn't a valid MIME type.
+    pub fn with_mime_type(
+        mut self,
+        mime_type: impl Into<String>,
+    ) -> std::result::Result<Self, ReqwestBuilderError> {
+        let mime_type = mime_type.into();
+        mime_type.parse::<mime::Mime>().map_err(|e| {
+            ReqwestBuilderError::InvalidRequest(format!("Invalid MIME type '{}': {}", mime_type, e))
+        })?;
+
+        self.mime_type = Some(mime_type);
+        Ok(self)
+    }
+
+    /// Size of the file content in bytes
+    pub fn len(&self) -> u64 {
+        match &self.content {
+            FileContent::Bytes(bytes) => bytes.len() as u64,
+            FileContent::Path { len, .. } => *len,
+        }
+    }
+
+    /// Whether the file content is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Convert into a multipart file part, carrying the filename and MIME type
+    ///
+    /// A MIME type that fails to parse is left off the part, and a file that
+    /// can't be read is sent empty; use [`FileUpload::try_to_multipart_part`]
+    /// to report either instead.
+    pub fn to_multipart_part(&self) -> reqwest::multipart::Part {
+        self.try_to_multipart_part()
+            .or_else(|_| self.bare_multipart_part())
+            .unwrap_or_else(|_| {
+                reqwest::multipart::Part::bytes(Vec::new()).file_name(self.filename.clone())
+            })
+    }
+
+    /// Convert into a multipart file part, carrying the filename and MIME type
+    ///
+    /// Returns `ReqwestBuilderError::MultipartError` if the MIME type doesn't
+    /// parse, or if `Path` content can't be read without the `stream` feature.
+    pub fn try_to_multipart_part(
+        &self,
+    ) -> std::result::Result<reqwest::multipart::Part, ReqwestBuilderError> {
+        let part = self.bare_multipart_part()?;
+        match &self.mime_type {
+            Some(mime_type) => part.mime_str(mime_type).map_err(|e| {
+                ReqwestBuilderError::MultipartError(format!(
+                    "Invalid MIME type '{}' for file '{}': {}",
+                    mime_type, self.filename, e
+                ))
+            }),
+            None => Ok(part),
+        }
+    }
+
+    // The part with its content and filename, but no MIME type
+    fn bare_multipart_part(
+        &self,
+    ) -> std::result::Result<reqwest::multipart::Part, ReqwestBuilderError> {
+        let part = match &self.content {
+            FileContent::Bytes(bytes) => reqwest::multipart::Part::bytes(bytes.clone()),
+            #[cfg(feature = "stream")]
+            FileContent::Path { path, len } => {
+                reqwest::multipart::Part::stream_with_length(stream_file(path.clone()), *len)
+            }
+            #[cfg(not(feature = "stream"))]
+            FileContent::Path { path, .. } => {
+                let bytes = std::fs::read(path).map_err(|e| {
+                    ReqwestBuilderError::MultipartError(format!(
+                        "Failed to read file '{}': {}",
+                        path.display(),
+                        e
+                    ))
+                })?;
+                reqwest::multipart::Part::bytes(bytes)
+            }
+        };
+        Ok(part.file_name(self.filename.clone()))
+    }
+}
+
+fn filename_from_path(path: &Path) -> String {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("file")
+        .to_string()
+}
+
+fn mime_type_from_path(path: &Path) -> Option<String> {
+    mime_guess::from_path(path)
+        .first()
+        .map(|mime| mime.to_string())
+}
+
+/// Lazily open a file and stream it as a request body
+#[cfg(feature = "stream")]
+fn stream_file(path: PathBuf) -> reqwest::Body {
+    use futures_util::{StreamExt, TryStreamExt};
+
+    let stream = futures_util::stream::once(tokio::fs::File::open(path))
+        .map_ok(tokio_util::io::ReaderStream::new)
+        .try_flatten();
+
+    reqwest::Body::wrap_stream(stream.boxed())
 }
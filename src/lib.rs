@@ -6,9 +6,11 @@
 //! ## Features
 //!
 //! - **Builder Pattern**: Trait-based approach for converting request structures into reqwest builders
-//! - **Multiple Body Types**: Support for JSON, form-encoded, multipart, and no-body requests
+//! - **Multiple Body Types**: Support for JSON, form-encoded, multipart, plain-text, raw bytes, NDJSON, GraphQL, and no-body requests
 //! - **Error Handling**: Comprehensive error handling with detailed error messages
 //! - **File Uploads**: Built-in support for file uploads with MIME type detection
+//!   (streamed from disk with the `stream` feature)
+//! - **Body Compression**: Opt-in gzip compression of JSON and form bodies (`gzip` feature)
 //! - **Header Management**: Safe header serialization with proper error reporting
 //!
 //! ## Quick Start
@@ -39,6 +41,8 @@
 // Core modules
 pub mod errors;
 pub mod file_upload;
+pub mod prelude;
+pub mod response;
 pub mod serialization;
 pub mod trait_impl;
 pub mod types;
@@ -48,10 +52,30 @@ pub mod types;
 pub use reqwest_builder_derive::*;
 
 // Re-exports for convenience
-pub use errors::ReqwestBuilderError;
-pub use file_upload::FileUpload;
-pub use trait_impl::{IntoReqwestBuilder, QueryParamValue, query_param_helper};
-pub use types::{QueryParams, RequestBody};
+pub use errors::{ErrorSource, ReqwestBuilderError};
+pub use file_upload::{FileContent, FileUpload};
+pub use response::{handle_response, handle_response_parsed, link_relation, next_page_token};
+pub use trait_impl::{
+    CommaSeparated, DynIntoReqwestBuilder, IntoQueryParams, IntoReqwestBuilder, QueryParamDispatch,
+    QueryParamValue, QueryParamViaDisplay, QueryParamViaValue, basic_auth_value,
+    multipart_text_helper, ndjson_records_helper, query_param_helper, websocket_key,
+};
+pub use types::{
+    Cookies, FormArrayStyle, GraphQLBody, JsonPatch, PatchOperation, QueryParams, RequestBody,
+};
 
 // Re-export serialization functions for advanced users
-pub use serialization::{construct_url, serialize_to_form_params, serialize_to_header_map};
+pub use serialization::{
+    append_raw_query_params, construct_url, construct_url_typed, encode_path_param,
+    encode_query_string, form_value_to_string, has_json_body_content, mark_sensitive_headers,
+    merge_query_params, serialize_to_form_pairs, serialize_to_form_params,
+    serialize_to_form_params_nested, serialize_to_header_map, serialize_to_query_params,
+    try_encode_path_param, validate_body_shape, validate_query_params,
+};
+
+#[cfg(feature = "gzip")]
+pub use serialization::gzip_compress;
+#[cfg(feature = "serde_qs")]
+pub use serialization::serialize_to_nested_query_params;
+#[cfg(feature = "stream")]
+pub use serialization::stream_body;
@@ -0,0 +1,17 @@
+//! Commonly used items, for a single glob import
+//!
+//! ```rust
+//! use reqwest_builder::prelude::*;
+//! ```
+//!
+//! With the `derive` feature this also brings the `IntoReqwestBuilder` derive
+//! macro into scope, alongside the trait of the same name.
+
+pub use crate::errors::ReqwestBuilderError;
+pub use crate::file_upload::{FileContent, FileUpload};
+pub use crate::response::handle_response;
+pub use crate::trait_impl::IntoReqwestBuilder;
+pub use crate::types::{QueryParams, RequestBody};
+
+#[cfg(feature = "derive")]
+pub use reqwest_builder_derive::IntoReqwestBuilder;
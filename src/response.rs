@@ -0,0 +1,107 @@
+use crate::errors::ReqwestBuilderError;
+use serde::de::DeserializeOwned;
+
+/// Turn a response into the deserialized success type or a structured error
+///
+/// The body is read in full. 2xx responses are deserialized from JSON into
+/// `T`; any other status is reported as `ReqwestBuilderError::ResponseError`
+/// carrying the status code and the body text.
+pub async fn handle_response<T: DeserializeOwned>(
+    response: reqwest::Response,
+) -> std::result::Result<T, ReqwestBuilderError> {
+    let status = response.status();
+    let body = response.text().await?;
+
+    if !status.is_success() {
+        return Err(ReqwestBuilderError::ResponseError {
+            status: status.as_u16(),
+            body,
+        });
+    }
+
+    Ok(serde_json::from_str(&body)?)
+}
+
+/// Like `handle_response`, but parse error bodies as the API's error type `E`
+///
+/// A non-2xx body that deserializes into `E` is reported as
+/// `ReqwestBuilderError::ApiError` with the `Debug` form of the parsed error;
+/// any other body falls back to `ReqwestBuilderError::ResponseError`.
+pub async fn handle_response_parsed<T, E>(
+    response: reqwest::Response,
+) -> std::result::Result<T, ReqwestBuilderError>
+where
+    T: DeserializeOwned,
+    E: DeserializeOwned + std::fmt::Debug,
+{
+    let status = response.status();
+    let body = response.text().await?;
+
+    if !status.is_success() {
+        return Err(match serde_json::from_str::<E>(&body) {
+            Ok(error) => ReqwestBuilderError::ApiError {
+                status: status.as_u16(),
+                error: format!("{:?}", error),
+            },
+            Err(_) => ReqwestBuilderError::ResponseError {
+                status: status.as_u16(),
+                body,
+            },
+        });
+    }
+
+    Ok(serde_json::from_str(&body)?)
+}
+
+/// Cursor for the next page of a paginated response, from its headers
+///
+/// Looks for an RFC 8288 `Link` header with `rel="next"` and returns its
+/// target URL, falling back to the value of an `X-Next-Cursor` header.
+/// Returns `None` on the last page. For other relations or header names,
+/// use `link_relation` or read the header directly.
+pub fn next_page_token(headers: &http::HeaderMap) -> Option<String> {
+    link_relation(headers, "next").or_else(|| {
+        headers
+            .get("X-Next-Cursor")
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    })
+}
+
+/// Target of the first `Link` header entry with the given relation type
+///
+/// Parses every `Link` header as a list of `<target>; param=value` entries;
+/// `rel` may be quoted and list several space-separated relations, and is
+/// compared case-insensitively, e.g. `rel="next last"` matches `"next"`.
+pub fn link_relation(headers: &http::HeaderMap, rel: &str) -> Option<String> {
+    headers
+        .get_all(http::header::LINK)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .find_map(|value| find_link(value, rel))
+}
+
+fn find_link(value: &str, rel: &str) -> Option<String> {
+    let mut rest = value;
+    while let Some(start) = rest.find('<') {
+        let after_start = &rest[start + 1..];
+        let end = after_start.find('>')?;
+        let target = &after_start[..end];
+
+        // The parameters run until the next entry's `<`
+        let tail = &after_start[end + 1..];
+        let params_end = tail.find('<').unwrap_or(tail.len());
+        let has_rel = tail[..params_end]
+            .split([';', ','])
+            .filter_map(|param| param.split_once('='))
+            .filter(|(key, _)| key.trim().eq_ignore_ascii_case("rel"))
+            .flat_map(|(_, value)| value.trim().trim_matches('"').split_ascii_whitespace())
+            .any(|relation| relation.eq_ignore_ascii_case(rel));
+        if has_rel {
+            return Some(target.to_string());
+        }
+
+        rest = &tail[params_end..];
+    }
+    None
+}
@@ -1,85 +1,544 @@
-use crate::errors::ReqwestBuilderError;
+use crate::{
+    errors::{ErrorSource, ReqwestBuilderError},
+    types::{FormArrayStyle, QueryParams, RequestBody},
+};
 use http::HeaderMap;
+use percent_encoding::{AsciiSet, CONTROLS, NON_ALPHANUMERIC, utf8_percent_encode};
 use serde::Serialize;
 use std::collections::HashMap;
 
+/// Characters percent-encoded in a single URL path segment
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'<')
+    .add(b'>')
+    .add(b'`')
+    .add(b'?')
+    .add(b'{')
+    .add(b'}')
+    .add(b'/')
+    .add(b'%');
+
+/// Characters percent-encoded in a query key or value: all but RFC 3986's unreserved
+const QUERY_COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Percent-encode a value for use as a single URL path segment
+///
+/// `.` and `..` are returned as-is and are resolved as dot-segments when the
+/// URL is built, even percent-encoded; use `try_encode_path_param` to reject them.
+pub fn encode_path_param(value: &str) -> String {
+    utf8_percent_encode(value, PATH_SEGMENT).to_string()
+}
+
+/// Like `encode_path_param`, but reject `.` and `..` with `UrlError`
+///
+/// URL parsing resolves those segments (as well as `%2E` and `%2E%2E`), so
+/// `users/{id}/posts` with `id = ".."` would otherwise become `/posts`.
+pub fn try_encode_path_param(value: &str) -> std::result::Result<String, ReqwestBuilderError> {
+    if value == "." || value == ".." {
+        return Err(ReqwestBuilderError::UrlError(format!(
+            "Path parameter {:?} is a dot-segment and would change the request path",
+            value
+        )));
+    }
+    Ok(encode_path_param(value))
+}
+
 /// Convert a serializable type to form parameters with proper error handling
 pub fn serialize_to_form_params<T: Serialize>(
     data: &T,
 ) -> std::result::Result<HashMap<String, String>, ReqwestBuilderError> {
     let value = serde_json::to_value(data)?;
 
-    let obj = value.as_object().ok_or_else(|| {
-        ReqwestBuilderError::SerializationError("Data must serialize to a JSON object".to_string())
+    let obj = value
+        .as_object()
+        .ok_or_else(|| ReqwestBuilderError::SerializationError {
+            message: "Data must serialize to a JSON object".to_string(),
+            source: None,
+        })?;
+
+    let mut params = HashMap::new();
+    for (key, val) in obj {
+        if let Some(value_str) = form_value_to_string(val) {
+            params.insert(key.clone(), value_str);
+        }
+    }
+
+    Ok(params)
+}
+
+/// Serialize a flat struct into query parameters with `serde_urlencoded`
+///
+/// Respects serde attributes such as `rename` and `skip`, and skips `None`
+/// fields. Nested structs and sequences aren't supported by the format and
+/// are reported as a `SerializationError`.
+pub fn serialize_to_query_params<T: Serialize>(
+    data: &T,
+) -> std::result::Result<QueryParams, ReqwestBuilderError> {
+    let encoded = serde_urlencoded::to_string(data).map_err(|err| {
+        ReqwestBuilderError::SerializationError {
+            message: err.to_string(),
+            source: Some(ErrorSource::new(err)),
+        }
     })?;
 
+    Ok(url::form_urlencoded::parse(encoded.as_bytes())
+        .into_owned()
+        .collect())
+}
+
+/// Serialize a struct into query parameters with `serde_qs`
+///
+/// Nested structs and sequences become bracketed keys, so
+/// `{"filter": {"status": "active"}, "ids": [1, 2]}` yields `filter[status]`,
+/// `ids[0]` and `ids[1]` parameters. Serialization failures are reported as a
+/// `SerializationError`.
+#[cfg(feature = "serde_qs")]
+pub fn serialize_to_nested_query_params<T: Serialize>(
+    data: &T,
+) -> std::result::Result<QueryParams, ReqwestBuilderError> {
+    let encoded =
+        serde_qs::to_string(data).map_err(|err| ReqwestBuilderError::SerializationError {
+            message: err.to_string(),
+            source: Some(ErrorSource::new(err)),
+        })?;
+
+    Ok(url::form_urlencoded::parse(encoded.as_bytes())
+        .into_owned()
+        .collect())
+}
+
+/// Convert a serializable type to ordered form pairs, expanding arrays per `style`
+///
+/// Arrays are sent as repeated `key=` or `key[]=` entries, or as one JSON
+/// string with [`FormArrayStyle::Json`]. Other values are rendered with
+/// [`form_value_to_string`]. Pairs are ordered by key, and `null` values,
+/// including array elements, are skipped.
+pub fn serialize_to_form_pairs<T: Serialize>(
+    data: &T,
+    style: FormArrayStyle,
+) -> std::result::Result<Vec<(String, String)>, ReqwestBuilderError> {
+    let value = serde_json::to_value(data)?;
+
+    let obj = value
+        .as_object()
+        .ok_or_else(|| ReqwestBuilderError::SerializationError {
+            message: "Data must serialize to a JSON object".to_string(),
+            source: None,
+        })?;
+
+    let mut pairs = Vec::new();
+    for (key, val) in obj {
+        match (val, style) {
+            (serde_json::Value::Array(values), FormArrayStyle::Repeat) => {
+                pairs.extend(
+                    values
+                        .iter()
+                        .filter_map(form_value_to_string)
+                        .map(|value_str| (key.clone(), value_str)),
+                );
+            }
+            (serde_json::Value::Array(values), FormArrayStyle::Brackets) => {
+                pairs.extend(
+                    values
+                        .iter()
+                        .filter_map(form_value_to_string)
+                        .map(|value_str| (format!("{key}[]"), value_str)),
+                );
+            }
+            _ => {
+                if let Some(value_str) = form_value_to_string(val) {
+                    pairs.push((key.clone(), value_str));
+                }
+            }
+        }
+    }
+
+    Ok(pairs)
+}
+
+/// Convert a serializable type to form parameters, expanding nested values
+///
+/// Unlike [`serialize_to_form_params`], nested objects and arrays are flattened
+/// into Rails-style bracketed keys instead of being sent as JSON strings, so
+/// `{"user": {"name": "x"}, "tags": ["a", "b"]}` becomes `user[name]=x`,
+/// `tags[0]=a` and `tags[1]=b`. `null` values are skipped at any depth.
+pub fn serialize_to_form_params_nested<T: Serialize>(
+    data: &T,
+) -> std::result::Result<HashMap<String, String>, ReqwestBuilderError> {
+    let value = serde_json::to_value(data)?;
+
+    let obj = value
+        .as_object()
+        .ok_or_else(|| ReqwestBuilderError::SerializationError {
+            message: "Data must serialize to a JSON object".to_string(),
+            source: None,
+        })?;
+
     let mut params = HashMap::new();
     for (key, val) in obj {
-        let value_str = match val {
-            serde_json::Value::String(s) => s.clone(),
-            serde_json::Value::Number(n) => n.to_string(),
-            serde_json::Value::Bool(b) => b.to_string(),
-            serde_json::Value::Null => continue, // Skip null values
-            _ => val.to_string(),                // Arrays and objects as JSON strings
-        };
-        params.insert(key.clone(), value_str);
+        flatten_form_value(key.clone(), val, &mut params);
     }
 
     Ok(params)
 }
 
+fn flatten_form_value(key: String, val: &serde_json::Value, params: &mut HashMap<String, String>) {
+    match val {
+        serde_json::Value::Object(obj) => {
+            for (child_key, child) in obj {
+                flatten_form_value(format!("{key}[{child_key}]"), child, params);
+            }
+        }
+        serde_json::Value::Array(values) => {
+            for (index, child) in values.iter().enumerate() {
+                flatten_form_value(format!("{key}[{index}]"), child, params);
+            }
+        }
+        scalar => {
+            if let Some(value_str) = form_value_to_string(scalar) {
+                params.insert(key, value_str);
+            }
+        }
+    }
+}
+
+/// Render a single JSON value as a form field value
+///
+/// Returns `None` for `null` so absent optional fields are skipped.
+///
+/// Integers are kept as integers, so `i64::MAX` and `u64::MAX` are sent
+/// exactly. Floats use serde_json's shortest round-trip form, which always has
+/// a fractional part or exponent: `1.0` is sent as `1.0` and `0.1` as `0.1`.
+/// Query parameters are formatted with `Display` instead, where `1.0_f64` is `1`.
+pub fn form_value_to_string(val: &serde_json::Value) -> Option<String> {
+    match val {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        serde_json::Value::Bool(b) => Some(b.to_string()),
+        serde_json::Value::Null => None, // Skip null values
+        _ => Some(val.to_string()),      // Arrays and objects as JSON strings
+    }
+}
+
+/// Check whether a serialized JSON body carries any meaningful content
+///
+/// Objects count as empty when every value is `null`; any other value
+/// (arrays, strings, numbers, ...) is considered content.
+pub fn has_json_body_content(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Object(obj) => obj.values().any(|val| !val.is_null()),
+        serde_json::Value::Null => false,
+        _ => true,
+    }
+}
+
+/// Check that a serialized body has a shape the body type can send
+///
+/// JSON bodies must be an object or array (or `null`, meaning no body) and
+/// form bodies must be an object. Other body types aren't built from the
+/// serialized struct and are always accepted.
+pub fn validate_body_shape(
+    body: RequestBody,
+    value: &serde_json::Value,
+) -> std::result::Result<(), ReqwestBuilderError> {
+    let (valid, expected) = match body {
+        RequestBody::Json => (
+            value.is_object() || value.is_array() || value.is_null(),
+            "an object or array",
+        ),
+        RequestBody::Form => (value.is_object(), "an object"),
+        _ => (true, ""),
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(ReqwestBuilderError::InvalidRequest(format!(
+            "{:?} body must serialize to {}, got {}",
+            body, expected, value
+        )))
+    }
+}
+
 /// Convert serializable headers to HeaderMap with proper error handling
+///
+/// `null` values (e.g. `None` optional headers) are skipped, and arrays (e.g.
+/// `Vec<String>` fields) add one value per element under the same name. An
+/// array of bytes (e.g. a `Vec<u8>` field) is instead a single opaque value,
+/// which may contain non-UTF-8 bytes such as latin-1 text. Names are
+/// normalized to lowercase by `http::HeaderName`; the casing sent on the wire
+/// is chosen by the client (see `reqwest::ClientBuilder::http1_title_case_headers`).
 pub fn serialize_to_header_map<T: Serialize>(
     headers: &T,
 ) -> std::result::Result<HeaderMap, ReqwestBuilderError> {
     let mut header_map = HeaderMap::new();
     let value = serde_json::to_value(headers)?;
 
-    let obj = value.as_object().ok_or_else(|| {
-        ReqwestBuilderError::SerializationError(
-            "Headers must serialize to a JSON object".to_string(),
-        )
-    })?;
+    let obj = value
+        .as_object()
+        .ok_or_else(|| ReqwestBuilderError::SerializationError {
+            message: "Headers must serialize to a JSON object".to_string(),
+            source: None,
+        })?;
 
     for (key, val) in obj {
-        if let Some(val_str) = val.as_str() {
-            let header_name = http::HeaderName::from_bytes(key.as_bytes()).map_err(|e| {
-                ReqwestBuilderError::HeaderError {
-                    key: key.clone(),
-                    value: val_str.to_string(),
-                    source: format!("Invalid header name: {}", e),
+        match val {
+            serde_json::Value::Null => continue, // Skip absent optional headers
+            serde_json::Value::Array(values) if header_bytes(val).is_none() => {
+                // Each element becomes a separate value under the same name
+                for value in values {
+                    let (header_name, header_value) = header_pair(key, value)?;
+                    header_map.append(header_name, header_value);
                 }
-            })?;
+            }
+            value => {
+                let (header_name, header_value) = header_pair(key, value)?;
+                header_map.insert(header_name, header_value);
+            }
+        }
+    }
 
-            let header_value = http::HeaderValue::from_str(val_str).map_err(|e| {
-                ReqwestBuilderError::HeaderError {
-                    key: key.clone(),
-                    value: val_str.to_string(),
-                    source: format!("Invalid header value: {}", e),
-                }
-            })?;
+    Ok(header_map)
+}
 
-            header_map.insert(header_name, header_value);
-        } else {
+/// Mark the values of matching headers as sensitive
+///
+/// Sensitive values are redacted from `Debug` output and may be treated
+/// specially by HTTP/2 header compression.
+pub fn mark_sensitive_headers(
+    header_map: &mut HeaderMap,
+    is_sensitive: impl Fn(&http::HeaderName) -> bool,
+) {
+    for (name, value) in header_map.iter_mut() {
+        if is_sensitive(name) {
+            value.set_sensitive(true);
+        }
+    }
+}
+
+/// Validate a single serialized header name and value
+///
+/// Values are strings, or byte arrays for opaque (e.g. latin-1) values.
+fn header_pair(
+    key: &str,
+    val: &serde_json::Value,
+) -> std::result::Result<(http::HeaderName, http::HeaderValue), ReqwestBuilderError> {
+    let (display_value, header_value) = match (val.as_str(), header_bytes(val)) {
+        (Some(val_str), _) => (val_str.to_string(), http::HeaderValue::from_str(val_str)),
+        (None, Some(bytes)) => (
+            String::from_utf8_lossy(&bytes).into_owned(),
+            http::HeaderValue::from_bytes(&bytes),
+        ),
+        (None, None) => {
             return Err(ReqwestBuilderError::HeaderError {
-                key: key.clone(),
+                key: key.to_string(),
                 value: val.to_string(),
-                source: "Header value must be a string".to_string(),
+                source: "Header value must be a string or an array of bytes".to_string(),
             });
         }
-    }
+    };
 
-    Ok(header_map)
+    let header_name = http::HeaderName::from_bytes(key.as_bytes()).map_err(|e| {
+        ReqwestBuilderError::HeaderError {
+            key: key.to_string(),
+            value: display_value.clone(),
+            source: format!("Invalid header name: {}", e),
+        }
+    })?;
+
+    let header_value = header_value.map_err(|e| ReqwestBuilderError::HeaderError {
+        key: key.to_string(),
+        value: display_value,
+        source: format!("Invalid header value: {}", e),
+    })?;
+
+    Ok((header_name, header_value))
+}
+
+/// The bytes of a non-empty array of integers in `0..=255`, e.g. a `Vec<u8>` field
+fn header_bytes(val: &serde_json::Value) -> Option<Vec<u8>> {
+    let values = val.as_array().filter(|values| !values.is_empty())?;
+    values
+        .iter()
+        .map(|value| value.as_u64().and_then(|byte| u8::try_from(byte).ok()))
+        .collect()
 }
 
 /// Construct a URL by combining base URL and endpoint
+///
+/// Any path on the base URL is kept as a prefix (`https://api.example.com/v2`
+/// and `https://api.example.com/v2/` both resolve `/users` to `.../v2/users`),
+/// and a query string on the base URL is preserved ahead of the endpoint's own.
+///
+/// An absolute endpoint with a scheme and host (e.g. a pagination `next` link)
+/// is returned unchanged and `base_url` is ignored.
+///
+/// If the two can't be joined (e.g. a `mailto:` base URL) the strings are
+/// concatenated; use [`construct_url_typed`] to get an error instead.
 pub fn construct_url(base_url: &url::Url, endpoint: &str) -> String {
-    let base_str = base_url.as_str().trim_end_matches('/');
+    join_url(base_url, endpoint).unwrap_or_else(|_| {
+        format!(
+            "{}/{}",
+            base_url.as_str().trim_end_matches('/'),
+            endpoint.trim_start_matches('/')
+        )
+    })
+}
+
+/// Construct and parse a URL by combining base URL and endpoint
+///
+/// Joins like [`construct_url`], but returns a parsed `url::Url` and reports
+/// combinations that don't form a valid URL as `UrlError`. Note that a
+/// parsed URL always has a path, so an empty endpoint yields `.../` rather
+/// than the bare base URL.
+pub fn construct_url_typed(
+    base_url: &url::Url,
+    endpoint: &str,
+) -> std::result::Result<url::Url, ReqwestBuilderError> {
+    join_url(base_url, endpoint)
+        .and_then(|url| url::Url::parse(&url))
+        .map_err(|e| {
+            ReqwestBuilderError::UrlError(format!(
+                "Cannot join {:?} onto {}: {}",
+                endpoint, base_url, e
+            ))
+        })
+}
+
+fn join_url(base_url: &url::Url, endpoint: &str) -> std::result::Result<String, url::ParseError> {
+    // `users:search` parses as a URL too, so require a host to count as absolute
+    if url::Url::parse(endpoint).is_ok_and(|url| url.has_host()) {
+        return Ok(endpoint.to_string());
+    }
+
     let endpoint_str = endpoint.trim_start_matches('/');
 
+    let mut base = base_url.clone();
+    let base_query = base.query().map(str::to_string);
+    base.set_query(None);
+    base.set_fragment(None);
+
     if endpoint_str.is_empty() {
-        return base_str.to_string();
+        let base_str = base.as_str().trim_end_matches('/');
+        return Ok(match base_query {
+            Some(query) => format!("{base_str}?{query}"),
+            None => base_str.to_string(),
+        });
+    }
+
+    // Treat the base path as a directory so `join` appends instead of replacing
+    if !base.path().ends_with('/') {
+        let path = format!("{}/", base.path());
+        base.set_path(&path);
+    }
+
+    // The "./" prefix keeps endpoints like "users:search" from parsing as a scheme
+    let mut url = base.join(&format!("./{endpoint_str}"))?;
+
+    if let Some(base_query) = base_query {
+        let query = match url.query() {
+            Some(endpoint_query) if !endpoint_query.is_empty() => {
+                format!("{base_query}&{endpoint_query}")
+            }
+            _ => base_query,
+        };
+        url.set_query(Some(&query));
+    }
+
+    Ok(url.to_string())
+}
+
+/// Check that query parameters can be sent as given
+///
+/// Values may contain any characters (they are percent-encoded), but a key
+/// must not be empty, since `?=value` is meaningless to most servers.
+pub fn validate_query_params(params: &QueryParams) -> std::result::Result<(), ReqwestBuilderError> {
+    if let Some((key, _)) = params.iter().find(|(key, _)| key.is_empty()) {
+        return Err(ReqwestBuilderError::QueryError {
+            key: key.clone(),
+            reason: "Query parameter key must not be empty".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Append query parameters to a URL, keeping any query string it already has
+///
+/// Parameters are form-encoded and joined with `&`, so an endpoint like
+/// `/search?q=foo` plus `page=2` becomes `/search?q=foo&page=2`.
+pub fn merge_query_params(url: &mut url::Url, params: &QueryParams) {
+    if params.is_empty() {
+        return;
     }
 
-    format!("{base_str}/{endpoint_str}")
+    url.query_pairs_mut().extend_pairs(params);
+}
+
+/// Render query parameters as a `key=value&...` string, in the order given
+///
+/// Keys and values are strictly percent-encoded as RFC 3986 query components,
+/// so a space becomes `%20` rather than the form-encoded `+`.
+pub fn encode_query_string(params: &QueryParams) -> String {
+    params
+        .iter()
+        .map(|(key, value)| {
+            format!(
+                "{}={}",
+                utf8_percent_encode(key, QUERY_COMPONENT),
+                utf8_percent_encode(value, QUERY_COMPONENT)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Append pre-encoded query parameters to a URL without encoding them again
+///
+/// Each pair is added as `key=value` after any existing query. The URL parser
+/// still encodes characters that can't appear in a query, but leaves `%`
+/// escapes alone.
+pub fn append_raw_query_params(url: &mut url::Url, params: &QueryParams) {
+    if params.is_empty() {
+        return;
+    }
+
+    let mut query = url.query().unwrap_or_default().to_string();
+    for (key, value) in params {
+        if !query.is_empty() {
+            query.push('&');
+        }
+        query.push_str(key);
+        query.push('=');
+        query.push_str(value);
+    }
+    url.set_query(Some(&query));
+}
+
+/// Wrap an `AsyncRead` source as a streaming request body
+///
+/// The reader is consumed in chunks as the request is sent, so large payloads
+/// are never held in memory at once.
+#[cfg(feature = "stream")]
+pub fn stream_body<R>(reader: R) -> reqwest::Body
+where
+    R: tokio::io::AsyncRead + Send + 'static,
+{
+    reqwest::Body::wrap_stream(tokio_util::io::ReaderStream::new(reader))
+}
+
+/// Gzip-compress a serialized request body
+#[cfg(feature = "gzip")]
+pub fn gzip_compress(data: &[u8]) -> std::result::Result<Vec<u8>, ReqwestBuilderError> {
+    use std::io::Write;
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(data)?;
+    Ok(encoder.finish()?)
 }
@@ -1,9 +1,17 @@
+#[cfg(feature = "gzip")]
+use crate::serialization::gzip_compress;
 use crate::{
     errors::ReqwestBuilderError,
-    serialization::{construct_url, serialize_to_form_params, serialize_to_header_map},
-    types::{QueryParams, RequestBody},
+    file_upload::FileUpload,
+    response::{handle_response, handle_response_parsed},
+    serialization::{
+        append_raw_query_params, construct_url_typed, encode_query_string, form_value_to_string,
+        has_json_body_content, mark_sensitive_headers, merge_query_params, serialize_to_form_pairs,
+        serialize_to_header_map, validate_body_shape, validate_query_params,
+    },
+    types::{Cookies, FormArrayStyle, GraphQLBody, JsonPatch, QueryParams, RequestBody},
 };
-use serde::Serialize;
+use serde::{Serialize, de::DeserializeOwned};
 use url::Url;
 
 /// Trait for converting request structures into reqwest builders
@@ -23,26 +31,382 @@ where
     /// Endpoint path for the request
     fn endpoint(&self) -> String;
 
+    /// Fallible `endpoint`, used when building the request
+    ///
+    /// Override this to reject path parameters that would change the route,
+    /// e.g. with `try_encode_path_param`; the derive macro does. Defaults to
+    /// `endpoint()`.
+    fn try_endpoint(&self) -> std::result::Result<String, ReqwestBuilderError> {
+        Ok(self.endpoint())
+    }
+
+    /// Complete URL for one-off requests such as signed or redirect URLs
+    ///
+    /// When set, it is used as-is instead of joining `base_url` and `endpoint()`.
+    /// Query parameters are still appended. An unparsable URL is a `UrlError`.
+    fn full_url(&self) -> Option<String> {
+        None
+    }
+
+    /// Render the query into the endpoint (or `full_url`) before the URL is built
+    ///
+    /// For APIs and signing schemes that need the query as part of the request
+    /// target. Parameters are encoded with `encode_query_string` (`%20` for a
+    /// space) instead of being form-encoded onto the parsed URL; raw params
+    /// are still appended verbatim.
+    fn embed_query_in_path(&self) -> bool {
+        false
+    }
+
     /// Optional headers for the request
     fn headers(&self) -> Option<Self::Headers> {
         None
     }
 
+    /// Optional pre-built headers, for callers that already have an `http::HeaderMap`
+    ///
+    /// Applied after `headers()` and before `cookies()` and `extra_headers()`.
+    /// On a duplicate name these values replace every typed value for that name.
+    fn header_map(&self) -> Option<http::HeaderMap> {
+        None
+    }
+
+    /// Optional headers known only at runtime (e.g. a per-call request ID)
+    ///
+    /// These are applied after `headers()`. On a duplicate name the extra
+    /// header replaces every typed value for that name; other headers are kept.
+    fn extra_headers(&self) -> Option<http::HeaderMap> {
+        None
+    }
+
+    /// Optional `Accept` header value, e.g. `application/json`
+    ///
+    /// Applied before `headers()`, so a typed `Accept` header takes precedence.
+    fn accept(&self) -> Option<&str> {
+        None
+    }
+
+    /// Optional `If-None-Match` value, e.g. a cached `"etag"`
+    ///
+    /// Like `accept()`, applied before `headers()`.
+    fn if_none_match(&self) -> Option<String> {
+        None
+    }
+
+    /// Optional `If-Modified-Since` value, an HTTP date such as
+    /// `Wed, 21 Oct 2015 07:28:00 GMT`
+    ///
+    /// Like `accept()`, applied before `headers()`.
+    fn if_modified_since(&self) -> Option<String> {
+        None
+    }
+
+    /// Optional `Idempotency-Key` value for safely retrying non-idempotent requests
+    ///
+    /// Like `accept()`, applied before `headers()`. The key must stay the same
+    /// for every attempt, so generate it once and keep it in the request
+    /// rather than creating a new one in this method.
+    fn idempotency_key(&self) -> Option<String> {
+        None
+    }
+
+    /// Optional cookies, sent as a single `Cookie` header
+    ///
+    /// Applied after `headers()`, replacing any typed `Cookie` header.
+    fn cookies(&self) -> Option<Cookies> {
+        None
+    }
+
+    /// Send the request as a WebSocket opening handshake (RFC 6455)
+    ///
+    /// Adds `Connection: Upgrade`, `Upgrade: websocket`,
+    /// `Sec-WebSocket-Version: 13` and a fresh `Sec-WebSocket-Key` before the
+    /// typed headers, which can add e.g. `Sec-WebSocket-Protocol`. The method
+    /// must be `GET`, otherwise building the request returns `InvalidRequest`.
+    fn websocket(&self) -> bool {
+        false
+    }
+
+    /// Per-request timeout, from sending the request until the response body is read
+    ///
+    /// Overrides the client's timeout. Requests that exceed it fail with
+    /// `ReqwestBuilderError::Timeout`.
+    fn timeout(&self) -> Option<std::time::Duration> {
+        None
+    }
+
     /// Request body type
     fn body(&self) -> RequestBody {
         RequestBody::Json
     }
 
-    /// Optional query parameters
+    /// Optional query parameters, sent in the order given
     fn query_params(&self) -> Option<QueryParams> {
         None
     }
 
+    /// Query parameters whose serialization can fail, used when building the request
+    ///
+    /// Defaults to `query_params()`. Override this instead when the parameters
+    /// are serialized with serde, so errors surface from `into_reqwest_builder`.
+    fn try_query_params(&self) -> std::result::Result<Option<QueryParams>, ReqwestBuilderError> {
+        Ok(self.query_params())
+    }
+
+    /// Optional query parameters that are already percent-encoded
+    ///
+    /// Appended verbatim after all other parameters, so `%2F` stays `%2F`
+    /// instead of becoming `%252F`. Only characters that are never valid in
+    /// a query (such as spaces) are still encoded.
+    fn raw_query_params(&self) -> Option<QueryParams> {
+        None
+    }
+
+    /// Optional query parameters known only at runtime (e.g. a tracing tag)
+    ///
+    /// These are appended after `query_params()`. On a duplicate key the extra
+    /// params replace every derived value for that key; other params are kept.
+    fn extra_query_params(&self) -> Option<QueryParams> {
+        None
+    }
+
+    /// Send a JSON body even when it has no content
+    ///
+    /// By default a JSON body that serializes to `{}`, `null` or an object of
+    /// only `null`s is left off. When `true` it is sent as serialized, with
+    /// `null` sent as `{}`, for APIs that require a JSON object on every POST.
+    fn allow_empty_json_body(&self) -> bool {
+        false
+    }
+
+    /// Pretty-print `RequestBody::Json` bodies, for readable payloads while debugging
+    ///
+    /// The body is rendered from `serialize_body()`, bypassing `serialize_json_body()`.
+    fn pretty_json(&self) -> bool {
+        false
+    }
+
+    /// Gzip-compress JSON and form bodies and set `Content-Encoding: gzip`
+    #[cfg(feature = "gzip")]
+    fn compress_body(&self) -> bool {
+        false
+    }
+
+    /// Serialize the fields that make up a JSON or form body
+    ///
+    /// Defaults to the whole struct. The derive macro overrides this to leave
+    /// out path, query, header and file fields.
+    fn serialize_body(&self) -> std::result::Result<serde_json::Value, ReqwestBuilderError> {
+        Ok(serde_json::to_value(self)?)
+    }
+
+    /// JSON body assembled at runtime, sent verbatim instead of serializing `self`
+    ///
+    /// Used for `RequestBody::Json` when `Some`. The value isn't checked for
+    /// shape or emptiness, so `{}` or a bare string is sent as given.
+    fn json_value_body(&self) -> Option<serde_json::Value> {
+        None
+    }
+
+    /// Encode the JSON body into bytes
+    ///
+    /// Override this to use a different JSON encoder or `serde_json` settings.
+    /// It is only called when `serialize_body()` has content to send.
+    fn serialize_json_body(&self) -> std::result::Result<Vec<u8>, ReqwestBuilderError> {
+        Ok(serde_json::to_vec(&self.serialize_body()?)?)
+    }
+
+    /// Plain-text body content - override this for `RequestBody::Text`
+    fn text_body(&self) -> Option<String> {
+        None
+    }
+
+    /// Raw binary body content - override this for `RequestBody::Bytes`
+    ///
+    /// The caller is responsible for setting an appropriate `Content-Type` header.
+    fn bytes_body(&self) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// NDJSON records - override this for `RequestBody::Ndjson`
+    ///
+    /// Each record is serialized on its own line, terminated by a newline.
+    fn ndjson_records(&self) -> Option<Vec<serde_json::Value>> {
+        None
+    }
+
+    /// GraphQL envelope - override this for `RequestBody::GraphQL`
+    fn graphql_body(&self) -> Option<GraphQLBody> {
+        None
+    }
+
+    /// How array fields of a `RequestBody::Form` body are encoded
+    fn form_array_style(&self) -> FormArrayStyle {
+        FormArrayStyle::Json
+    }
+
+    /// JSON Patch document - override this for `RequestBody::JsonPatch`
+    fn json_patch(&self) -> Option<JsonPatch> {
+        None
+    }
+
+    /// XML body content - override this for `RequestBody::Xml`
+    fn xml_body(&self) -> Option<String> {
+        None
+    }
+
+    /// Streaming body - override this for `RequestBody::Stream`
+    ///
+    /// A stream can usually be read only once, so this is typically backed by
+    /// something like a `Mutex<Option<_>>` that is taken here. With the
+    /// `stream` feature, `stream_body` wraps `AsyncRead` sources.
+    fn body_stream(&self) -> Option<reqwest::Body> {
+        None
+    }
+
+    /// Files included in the multipart form, used for the `max_multipart_bytes` check
+    fn multipart_files(&self) -> Vec<&FileUpload> {
+        Vec::new()
+    }
+
+    /// Upper limit on the combined size of `multipart_files`, checked before the form is built
+    fn max_multipart_bytes(&self) -> Option<u64> {
+        None
+    }
+
     /// Create multipart form - override this for file uploads
     fn create_multipart_form(&self) -> Option<reqwest::multipart::Form> {
         None
     }
 
+    /// Multipart form whose construction can fail, used when building the request
+    ///
+    /// Defaults to `create_multipart_form()`. Override this instead to report
+    /// errors such as an invalid part as `ReqwestBuilderError::MultipartError`.
+    fn try_create_multipart_form(
+        &self,
+    ) -> std::result::Result<Option<reqwest::multipart::Form>, ReqwestBuilderError> {
+        Ok(self.create_multipart_form())
+    }
+
+    /// Signature header computed over the final request, e.g. an HMAC-SHA256
+    ///
+    /// Called once the URL (with its query), body and headers are in place,
+    /// with the serialized body bytes. Streamed bodies, such as multipart
+    /// forms, are passed as an empty slice. Returns the header name and value
+    /// to attach, or `None` to send the request unsigned.
+    fn sign(&self, method: &http::Method, url: &Url, body: &[u8]) -> Option<(String, String)> {
+        let _ = (method, url, body);
+        None
+    }
+
+    /// Final hook to adjust the builder, e.g. `.version(...)` or middleware extensions
+    ///
+    /// Called last, after the URL, body and headers are in place. Prefer the
+    /// declarative methods above where one fits.
+    fn customize(
+        &self,
+        builder: reqwest_middleware::RequestBuilder,
+    ) -> reqwest_middleware::RequestBuilder {
+        builder
+    }
+
+    /// Extra header names to treat as sensitive (case-insensitive)
+    ///
+    /// `Authorization`, `Proxy-Authorization` and `Cookie` are always sensitive.
+    /// Sensitive values are marked with `HeaderValue::set_sensitive`, so they are
+    /// redacted from `Debug` output, and are left out of `debug_summary`.
+    fn sensitive_headers(&self) -> &[&str] {
+        &[]
+    }
+
+    /// Canonical query string, e.g. for request signing or cache keys
+    ///
+    /// Renders `query_params`, `extra_query_params` and `raw_query_params` as
+    /// `a=1&b=2`, sorted by key and then value so the output doesn't depend on
+    /// field order. Values are percent-encoded like on the wire; raw params are
+    /// kept as given. Returns `None` when there are no parameters.
+    fn query_string(&self) -> Option<String> {
+        let encode = |part: &str| url::form_urlencoded::byte_serialize(part.as_bytes()).collect();
+        let mut pairs: Vec<(String, String)> = combined_query_params(self)
+            .unwrap_or_default()
+            .iter()
+            .map(|(key, value)| (encode(key), encode(value)))
+            .collect();
+        pairs.extend(self.raw_query_params().unwrap_or_default());
+        if pairs.is_empty() {
+            return None;
+        }
+
+        pairs.sort();
+        let rendered: Vec<String> = pairs
+            .into_iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect();
+        Some(rendered.join("&"))
+    }
+
+    /// One-line summary of the request for logging, without building it
+    ///
+    /// Includes the method, endpoint and query string followed by the typed,
+    /// runtime and cookie headers, e.g. `POST /users?draft=true [headers:
+    /// authorization(redacted), x-trace=abc]`. Values of sensitive headers
+    /// are replaced by `(redacted)`. The body is never included.
+    fn debug_summary(&self) -> String {
+        let target = self.full_url().unwrap_or_else(|| self.endpoint());
+        let mut summary = format!("{} {}", self.method(), target);
+
+        let params = combined_query_params(self).unwrap_or_default();
+        let mut query = url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(&params)
+            .finish();
+        for (key, value) in self.raw_query_params().unwrap_or_default() {
+            if !query.is_empty() {
+                query.push('&');
+            }
+            query.push_str(&format!("{key}={value}"));
+        }
+        if !query.is_empty() {
+            summary.push(if summary.contains('?') { '&' } else { '?' });
+            summary.push_str(&query);
+        }
+
+        let mut headers = self
+            .headers()
+            .and_then(|headers| serialize_to_header_map(&headers).ok())
+            .unwrap_or_default();
+        if let Some(header_map) = self.header_map() {
+            replace_headers(&mut headers, header_map);
+        }
+        if let Some(value) = self
+            .cookies()
+            .filter(|cookies| !cookies.0.is_empty())
+            .and_then(|cookies| cookies.header_value().ok())
+            .and_then(|value| http::HeaderValue::from_str(&value).ok())
+        {
+            headers.insert(http::header::COOKIE, value);
+        }
+        if let Some(extra_headers) = self.extra_headers() {
+            replace_headers(&mut headers, extra_headers);
+        }
+
+        if !headers.is_empty() {
+            let rendered: Vec<String> = headers
+                .iter()
+                .map(|(name, value)| {
+                    if is_sensitive_header(self, name) {
+                        format!("{}(redacted)", name)
+                    } else {
+                        format!("{}={}", name, value.to_str().unwrap_or("<binary>"))
+                    }
+                })
+                .collect();
+            summary.push_str(&format!(" [headers: {}]", rendered.join(", ")));
+        }
+
+        summary
+    }
+
     /// Convert the request into a reqwest builder with proper error handling
     ///
     /// This is the preferred method for new code as it provides proper error handling.
@@ -51,25 +415,286 @@ where
         client: &reqwest_middleware::ClientWithMiddleware,
         base_url: &Url,
     ) -> std::result::Result<reqwest_middleware::RequestBuilder, ReqwestBuilderError> {
-        // Construct URL with error handling
-        let url = construct_url(base_url, &self.endpoint());
-        let mut builder = client.request(self.method(), &url);
+        self.build_request(client, base_url)
+    }
+
+    /// Build a reqwest builder without consuming the request
+    ///
+    /// Useful when the same request has to be built more than once, e.g. to
+    /// retry an idempotent GET or to inspect a request before sending it.
+    /// Multipart bodies are rebuilt through `create_multipart_form` on every
+    /// call, so that method must be able to produce the form repeatedly; a
+    /// form with streamed parts can't be recovered from `try_clone` instead.
+    fn build_request(
+        &self,
+        client: &reqwest_middleware::ClientWithMiddleware,
+        base_url: &Url,
+    ) -> std::result::Result<reqwest_middleware::RequestBuilder, ReqwestBuilderError> {
+        let params = combined_query_params(self)?;
+        validate_query_params(&params)?;
+        let raw_params = self.raw_query_params().unwrap_or_default();
+        validate_query_params(&raw_params)?;
 
-        // Add query parameters if present
-        if let Some(params) = self.query_params() {
-            builder = builder.query(&params);
+        let url = if self.embed_query_in_path() {
+            // The query is part of the target string before it is parsed
+            let mut query = encode_query_string(&params);
+            for (key, value) in &raw_params {
+                if !query.is_empty() {
+                    query.push('&');
+                }
+                query.push_str(&format!("{key}={value}"));
+            }
+            let with_query = |mut target: String| {
+                if !query.is_empty() {
+                    target.push(if target.contains('?') { '&' } else { '?' });
+                    target.push_str(&query);
+                }
+                target
+            };
+            match self.full_url() {
+                Some(full_url) => Url::parse(&with_query(full_url))?,
+                None => construct_url_typed(base_url, &with_query(self.try_endpoint()?))?,
+            }
+        } else {
+            // Construct URL with error handling
+            let mut url = match self.full_url() {
+                Some(full_url) => Url::parse(&full_url)?,
+                None => construct_url_typed(base_url, &self.try_endpoint()?)?,
+            };
+
+            // Add query parameters if present, after any query already in the endpoint
+            merge_query_params(&mut url, &params);
+            append_raw_query_params(&mut url, &raw_params);
+            url
+        };
+
+        let mut builder = client.request(self.method(), url.clone());
+
+        if let Some(timeout) = self.timeout() {
+            builder = builder.timeout(timeout);
         }
 
-        // Handle request body with error handling
+        // Handle request body with error handling; this also sets the default
+        // Content-Type, which typed and runtime headers below may override
         builder = self.add_body_to_builder(builder)?;
 
+        let helper_headers = [
+            ("Accept", self.accept().map(str::to_string)),
+            ("If-None-Match", self.if_none_match()),
+            ("If-Modified-Since", self.if_modified_since()),
+            ("Idempotency-Key", self.idempotency_key()),
+        ];
+        for (key, value) in helper_headers {
+            let Some(value) = value else { continue };
+            let header_value = http::HeaderValue::from_str(&value).map_err(|e| {
+                ReqwestBuilderError::HeaderError {
+                    key: key.to_string(),
+                    value: value.clone(),
+                    source: format!("Invalid header value: {}", e),
+                }
+            })?;
+            builder = builder.header(key, header_value);
+        }
+
+        if self.websocket() {
+            if self.method() != http::Method::GET {
+                return Err(ReqwestBuilderError::InvalidRequest(format!(
+                    "WebSocket handshakes must use GET, got {}",
+                    self.method()
+                )));
+            }
+            builder = builder
+                .header(http::header::CONNECTION, "Upgrade")
+                .header(http::header::UPGRADE, "websocket")
+                .header(http::header::SEC_WEBSOCKET_VERSION, "13")
+                .header(http::header::SEC_WEBSOCKET_KEY, websocket_key());
+        }
+
         // Add headers with error handling
         if let Some(headers) = self.headers() {
-            let header_map = serialize_to_header_map(&headers)?;
+            let mut header_map = serialize_to_header_map(&headers)?;
+            mark_sensitive_headers(&mut header_map, |name| is_sensitive_header(self, name));
             builder = builder.headers(header_map);
         }
 
-        Ok(builder)
+        // Pre-built headers override typed headers with the same name
+        if let Some(mut header_map) = self.header_map() {
+            mark_sensitive_headers(&mut header_map, |name| is_sensitive_header(self, name));
+            builder = builder.headers(header_map);
+        }
+
+        if let Some(cookies) = self.cookies().filter(|cookies| !cookies.0.is_empty()) {
+            let value = cookies.header_value()?;
+            let mut header_value = http::HeaderValue::from_str(&value).map_err(|e| {
+                ReqwestBuilderError::HeaderError {
+                    key: "Cookie".to_string(),
+                    value: value.clone(),
+                    source: format!("Invalid header value: {}", e),
+                }
+            })?;
+            header_value.set_sensitive(true);
+            let mut cookie_header = http::HeaderMap::new();
+            cookie_header.insert(http::header::COOKIE, header_value);
+            builder = builder.headers(cookie_header);
+        }
+
+        // Runtime headers override typed headers with the same name
+        if let Some(mut extra_headers) = self.extra_headers() {
+            mark_sensitive_headers(&mut extra_headers, |name| is_sensitive_header(self, name));
+            builder = builder.headers(extra_headers);
+        }
+
+        // Sign over the request as it will be sent. Streamed bodies can't be
+        // cloned and are signed over an empty body; a request that clones but
+        // fails to build here fails the same way when it is sent
+        let signature = match builder.try_clone() {
+            Some(clone) => clone.build().ok().and_then(|request| {
+                let body = request
+                    .body()
+                    .and_then(reqwest::Body::as_bytes)
+                    .unwrap_or_default();
+                self.sign(request.method(), request.url(), body)
+            }),
+            None => self.sign(&self.method(), &url, &[]),
+        };
+        if let Some((key, value)) = signature {
+            let invalid = |source: String| ReqwestBuilderError::HeaderError {
+                key: key.clone(),
+                value: value.clone(),
+                source,
+            };
+            let name = http::HeaderName::from_bytes(key.as_bytes())
+                .map_err(|e| invalid(format!("Invalid header name: {}", e)))?;
+            let mut header_value = http::HeaderValue::from_str(&value)
+                .map_err(|e| invalid(format!("Invalid header value: {}", e)))?;
+            header_value.set_sensitive(is_sensitive_header(self, &name));
+            builder = builder.header(name, header_value);
+        }
+
+        Ok(self.customize(builder))
+    }
+
+    /// Render the request as a plain `http::Request` without a live client
+    ///
+    /// Useful for unit-testing request definitions: the result carries the
+    /// final method, URL, headers and serialized body. Streamed bodies, such
+    /// as multipart forms, can't be rendered and return `InvalidRequest`.
+    fn to_http_request(
+        self,
+        base_url: &Url,
+    ) -> std::result::Result<http::Request<Vec<u8>>, ReqwestBuilderError> {
+        let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+        let request = self.build_request(&client, base_url)?.build()?;
+
+        let body = match request.body() {
+            Some(body) => body
+                .as_bytes()
+                .ok_or_else(|| {
+                    ReqwestBuilderError::InvalidRequest(
+                        "Streamed request bodies can't be converted to http::Request".to_string(),
+                    )
+                })?
+                .to_vec(),
+            None => Vec::new(),
+        };
+
+        let mut http_request = http::Request::new(body);
+        *http_request.method_mut() = request.method().clone();
+        *http_request.uri_mut() = request
+            .url()
+            .as_str()
+            .parse()
+            .map_err(|e: http::uri::InvalidUri| ReqwestBuilderError::UrlError(e.to_string()))?;
+        *http_request.headers_mut() = request.headers().clone();
+
+        Ok(http_request)
+    }
+
+    /// Build the request and send it in one step
+    ///
+    /// Errors building the request are returned as-is, while transport
+    /// failures are reported as `ReqwestBuilderError::RequestError`.
+    fn send(
+        self,
+        client: &reqwest_middleware::ClientWithMiddleware,
+        base_url: &Url,
+    ) -> impl Future<Output = std::result::Result<reqwest::Response, ReqwestBuilderError>> + Send
+    {
+        let builder = self.into_reqwest_builder(client, base_url);
+
+        async move { Ok(builder?.send().await?) }
+    }
+
+    /// Send the request, retrying transient failures with exponential backoff
+    ///
+    /// Connection errors, timeouts, 5xx responses and 429 responses are retried
+    /// up to `max_retries` times, waiting 100ms, 200ms, 400ms, ... between
+    /// attempts, or the `Retry-After` seconds of a 429 response. The request
+    /// is rebuilt with `build_request` for every attempt, so only use this for
+    /// idempotent requests. Multipart and streaming requests are sent once and
+    /// never retried, since their bodies may not be rebuildable. When retries
+    /// run out the last response (or error) is returned.
+    #[cfg(feature = "tokio")]
+    fn send_with_retry(
+        self,
+        client: &reqwest_middleware::ClientWithMiddleware,
+        base_url: &Url,
+        max_retries: u32,
+    ) -> impl Future<Output = std::result::Result<reqwest::Response, ReqwestBuilderError>> + Send
+    where
+        Self: Send + Sync,
+    {
+        async move {
+            let rebuildable = !matches!(self.body(), RequestBody::Multipart | RequestBody::Stream);
+            let max_retries = if rebuildable { max_retries } else { 0 };
+
+            let mut attempt = 0;
+            loop {
+                let result = self.build_request(client, base_url)?.send().await;
+
+                match retry_delay(&result, attempt) {
+                    Some(delay) if attempt < max_retries => {
+                        tokio::time::sleep(delay).await;
+                        attempt += 1;
+                    }
+                    _ => return Ok(result?),
+                }
+            }
+        }
+    }
+
+    /// Send the request and deserialize a successful JSON response into `R`
+    ///
+    /// Non-2xx responses are reported as `ReqwestBuilderError::ResponseError`
+    /// with the response body text captured for debugging.
+    fn send_json<R: DeserializeOwned>(
+        self,
+        client: &reqwest_middleware::ClientWithMiddleware,
+        base_url: &Url,
+    ) -> impl Future<Output = std::result::Result<R, ReqwestBuilderError>> + Send {
+        let response = self.send(client, base_url);
+
+        async move { handle_response(response.await?).await }
+    }
+
+    /// Send the request, deserializing a 2xx JSON response into `R` and an
+    /// error response into the API's error type `E`
+    ///
+    /// Error bodies that parse as `E` are reported as
+    /// `ReqwestBuilderError::ApiError`; others fall back to
+    /// `ReqwestBuilderError::ResponseError` like `send_json`.
+    fn send_parsed<R, E>(
+        self,
+        client: &reqwest_middleware::ClientWithMiddleware,
+        base_url: &Url,
+    ) -> impl Future<Output = std::result::Result<R, ReqwestBuilderError>> + Send
+    where
+        R: DeserializeOwned,
+        E: DeserializeOwned + std::fmt::Debug,
+    {
+        let response = self.send(client, base_url);
+
+        async move { handle_response_parsed::<R, E>(response.await?).await }
     }
 
     /// Add body to the request builder with proper error handling
@@ -79,20 +704,111 @@ where
     ) -> std::result::Result<reqwest_middleware::RequestBuilder, ReqwestBuilderError> {
         match self.body() {
             RequestBody::Json => {
-                let json_str = serde_json::to_string(self).map_err(ReqwestBuilderError::from)?;
-                if json_str != "{}" {
-                    builder = builder.json(self);
+                let body = match self.json_value_body() {
+                    // A runtime value is sent verbatim, instead of the struct
+                    Some(value) if self.pretty_json() => Some(serde_json::to_vec_pretty(&value)?),
+                    Some(value) => Some(serde_json::to_vec(&value)?),
+                    None => {
+                        let value = self.serialize_body()?;
+                        validate_body_shape(RequestBody::Json, &value)?;
+                        if !has_json_body_content(&value) && !self.allow_empty_json_body() {
+                            None
+                        } else if value.is_null() {
+                            Some(b"{}".to_vec())
+                        } else if self.pretty_json() {
+                            Some(serde_json::to_vec_pretty(&value)?)
+                        } else {
+                            Some(self.serialize_json_body()?)
+                        }
+                    }
+                };
+                if let Some(body) = body {
+                    builder = with_default_content_type(builder, RequestBody::Json);
+
+                    #[cfg(feature = "gzip")]
+                    if self.compress_body() {
+                        return Ok(builder
+                            .header(http::header::CONTENT_ENCODING, "gzip")
+                            .body(gzip_compress(&body)?));
+                    }
+
+                    builder = builder.body(body);
                 }
             }
             RequestBody::Form => {
-                let params = serialize_to_form_params(self)?;
+                let value = self.serialize_body()?;
+                validate_body_shape(RequestBody::Form, &value)?;
+                let params = serialize_to_form_pairs(&value, self.form_array_style())?;
+
+                #[cfg(feature = "gzip")]
+                if self.compress_body() {
+                    let encoded = url::form_urlencoded::Serializer::new(String::new())
+                        .extend_pairs(&params)
+                        .finish();
+                    let body = gzip_compress(encoded.as_bytes())?;
+                    return Ok(with_default_content_type(builder, RequestBody::Form)
+                        .header(http::header::CONTENT_ENCODING, "gzip")
+                        .body(body));
+                }
+
                 builder = builder.form(&params);
             }
             RequestBody::Multipart => {
-                if let Some(form) = self.create_multipart_form() {
+                if let Some(limit) = self.max_multipart_bytes() {
+                    let total: u64 = self.multipart_files().iter().map(|file| file.len()).sum();
+                    if total > limit {
+                        return Err(ReqwestBuilderError::InvalidRequest(format!(
+                            "Multipart files total {} bytes, exceeding the limit of {} bytes",
+                            total, limit
+                        )));
+                    }
+                }
+
+                if let Some(form) = self.try_create_multipart_form()? {
                     builder = builder.multipart(form);
                 }
             }
+            RequestBody::Text => {
+                if let Some(text) = self.text_body() {
+                    builder = with_default_content_type(builder, RequestBody::Text).body(text);
+                }
+            }
+            RequestBody::Bytes => {
+                if let Some(bytes) = self.bytes_body() {
+                    builder = builder.body(bytes);
+                }
+            }
+            RequestBody::Ndjson => {
+                if let Some(records) = self.ndjson_records() {
+                    let mut body = String::new();
+                    for record in &records {
+                        body.push_str(&serde_json::to_string(record)?);
+                        body.push('\n');
+                    }
+                    builder = with_default_content_type(builder, RequestBody::Ndjson).body(body);
+                }
+            }
+            RequestBody::GraphQL => {
+                if let Some(graphql) = self.graphql_body() {
+                    builder = builder.json(&graphql);
+                }
+            }
+            RequestBody::JsonPatch => {
+                if let Some(patch) = self.json_patch() {
+                    builder = with_default_content_type(builder, RequestBody::JsonPatch)
+                        .body(serde_json::to_vec(&patch)?);
+                }
+            }
+            RequestBody::Xml => {
+                if let Some(xml) = self.xml_body() {
+                    builder = with_default_content_type(builder, RequestBody::Xml).body(xml);
+                }
+            }
+            RequestBody::Stream => {
+                if let Some(body) = self.body_stream() {
+                    builder = builder.body(body);
+                }
+            }
             RequestBody::None => {
                 // No body to add
             }
@@ -101,108 +817,353 @@ where
     }
 }
 
+/// Object-safe view of [`IntoReqwestBuilder`], for handling different request
+/// types through one trait object such as `Box<dyn DynIntoReqwestBuilder>`
+///
+/// `IntoReqwestBuilder` itself can't be a trait object because of its
+/// `Headers` type and `Sized + Serialize` bound. Every `IntoReqwestBuilder`
+/// type implements this trait, with the body serialized to a
+/// `serde_json::Value` instead of through the concrete type.
+pub trait DynIntoReqwestBuilder {
+    /// HTTP method for the request
+    fn erased_method(&self) -> http::Method;
+
+    /// Endpoint path for the request
+    fn erased_endpoint(&self) -> String;
+
+    /// The request body, as used for JSON and form bodies
+    fn erased_body(&self) -> std::result::Result<serde_json::Value, ReqwestBuilderError>;
+
+    /// Build the request, like [`IntoReqwestBuilder::build_request`]
+    fn erased_build_request(
+        &self,
+        client: &reqwest_middleware::ClientWithMiddleware,
+        base_url: &Url,
+    ) -> std::result::Result<reqwest_middleware::RequestBuilder, ReqwestBuilderError>;
+}
+
+impl<T: IntoReqwestBuilder> DynIntoReqwestBuilder for T {
+    fn erased_method(&self) -> http::Method {
+        self.method()
+    }
+
+    fn erased_endpoint(&self) -> String {
+        self.endpoint()
+    }
+
+    fn erased_body(&self) -> std::result::Result<serde_json::Value, ReqwestBuilderError> {
+        self.serialize_body()
+    }
+
+    fn erased_build_request(
+        &self,
+        client: &reqwest_middleware::ClientWithMiddleware,
+        base_url: &Url,
+    ) -> std::result::Result<reqwest_middleware::RequestBuilder, ReqwestBuilderError> {
+        self.build_request(client, base_url)
+    }
+}
+
+// How long to wait before retrying a result, or `None` if it isn't transient
+#[cfg(feature = "tokio")]
+fn retry_delay(
+    result: &std::result::Result<reqwest::Response, reqwest_middleware::Error>,
+    attempt: u32,
+) -> Option<std::time::Duration> {
+    let backoff = std::time::Duration::from_millis(100 * 2u64.saturating_pow(attempt));
+
+    match result {
+        Ok(response) if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS => {
+            let retry_after = response
+                .headers()
+                .get(http::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse().ok())
+                .map(std::time::Duration::from_secs);
+            Some(retry_after.unwrap_or(backoff))
+        }
+        Ok(response) if response.status().is_server_error() => Some(backoff),
+        Ok(_) => None,
+        Err(reqwest_middleware::Error::Reqwest(error))
+            if error.is_connect() || error.is_timeout() =>
+        {
+            Some(backoff)
+        }
+        Err(_) => None,
+    }
+}
+
+// Headers that are always sensitive, on top of `sensitive_headers`
+const DEFAULT_SENSITIVE_HEADERS: &[&str] = &["Authorization", "Proxy-Authorization", "Cookie"];
+
+fn is_sensitive_header<R: IntoReqwestBuilder>(request: &R, name: &http::HeaderName) -> bool {
+    DEFAULT_SENSITIVE_HEADERS
+        .iter()
+        .chain(request.sensitive_headers())
+        .any(|sensitive| name.as_str().eq_ignore_ascii_case(sensitive))
+}
+
+// Replace every value of each name in `overrides`, like `RequestBuilder::headers`
+fn replace_headers(headers: &mut http::HeaderMap, overrides: http::HeaderMap) {
+    for name in overrides.keys() {
+        headers.remove(name);
+    }
+    for (name, value) in &overrides {
+        headers.append(name.clone(), value.clone());
+    }
+}
+
+// Set the body's default `Content-Type`; typed and runtime headers applied
+// later replace it
+fn with_default_content_type(
+    builder: reqwest_middleware::RequestBuilder,
+    body: RequestBody,
+) -> reqwest_middleware::RequestBuilder {
+    match body.default_content_type() {
+        Some(content_type) => builder.header(http::header::CONTENT_TYPE, content_type),
+        None => builder,
+    }
+}
+
+// `try_query_params` followed by `extra_query_params`, which replace derived
+// values on a duplicate key
+fn combined_query_params<R: IntoReqwestBuilder>(
+    request: &R,
+) -> std::result::Result<QueryParams, ReqwestBuilderError> {
+    let mut params = request.try_query_params()?.unwrap_or_default();
+    if let Some(extra) = request.extra_query_params() {
+        params.retain(|(key, _)| !extra.iter().any(|(extra_key, _)| extra_key == key));
+        params.extend(extra);
+    }
+    Ok(params)
+}
+
 // Helper function for the derive macro to handle query parameters
-// This works with both Option and non-Option types
-pub fn query_param_helper<T>(
-    value: &T,
-    param_name: &str,
-    params: &mut std::collections::HashMap<String, String>,
-) where
+// This works with Option, non-Option and Vec types
+pub fn query_param_helper<T>(value: &T, param_name: &str, params: &mut QueryParams)
+where
     T: QueryParamValue,
 {
     value.add_to_params(param_name, params);
 }
 
+// Helper function for the derive macro to add a text part to a multipart form
+// Values are rendered like form fields; None values are skipped
+pub fn multipart_text_helper<T>(
+    form: reqwest::multipart::Form,
+    field_name: &str,
+    value: &T,
+) -> reqwest::multipart::Form
+where
+    T: Serialize,
+{
+    match serde_json::to_value(value)
+        .ok()
+        .as_ref()
+        .and_then(form_value_to_string)
+    {
+        Some(text) => form.text(field_name.to_string(), text),
+        None => form,
+    }
+}
+
+// Helper function for the derive macro to turn a body field into NDJSON records
+// Sequences yield one record per element; any other value is a single record
+pub fn ndjson_records_helper<T>(value: &T) -> Option<Vec<serde_json::Value>>
+where
+    T: Serialize,
+{
+    match serde_json::to_value(value).ok()? {
+        serde_json::Value::Array(records) => Some(records),
+        record => Some(vec![record]),
+    }
+}
+
+// Helper function for the derive macro to build a Basic `Authorization` header value
+// A missing password is encoded as an empty one, i.e. `username:`
+pub fn basic_auth_value<U, P>(username: U, password: Option<P>) -> String
+where
+    U: std::fmt::Display,
+    P: std::fmt::Display,
+{
+    use base64::Engine;
+
+    let credentials = match password {
+        Some(password) => format!("{}:{}", username, password),
+        None => format!("{}:", username),
+    };
+    format!(
+        "Basic {}",
+        base64::engine::general_purpose::STANDARD.encode(credentials)
+    )
+}
+
+/// Generate a random `Sec-WebSocket-Key`: 16 random bytes, base64-encoded
+pub fn websocket_key() -> String {
+    use base64::Engine;
+    use std::hash::{BuildHasher, Hasher};
+
+    // Each `RandomState` is seeded randomly, which is enough for a handshake nonce
+    let mut nonce = [0u8; 16];
+    for chunk in nonce.chunks_mut(8) {
+        let random = std::collections::hash_map::RandomState::new()
+            .build_hasher()
+            .finish();
+        chunk.copy_from_slice(&random.to_ne_bytes());
+    }
+    base64::engine::general_purpose::STANDARD.encode(nonce)
+}
+
 // Trait to handle different types of query parameter values
 pub trait QueryParamValue {
-    fn add_to_params(
-        &self,
-        param_name: &str,
-        params: &mut std::collections::HashMap<String, String>,
-    );
+    fn add_to_params(&self, param_name: &str, params: &mut QueryParams);
 }
 
-// Implementation for Option types
-impl<T: std::fmt::Display> QueryParamValue for Option<T> {
-    fn add_to_params(
-        &self,
-        param_name: &str,
-        params: &mut std::collections::HashMap<String, String>,
-    ) {
+// Option types send the inner value's form, and nothing when `None`
+impl<T: QueryParamValue> QueryParamValue for Option<T> {
+    fn add_to_params(&self, param_name: &str, params: &mut QueryParams) {
         if let Some(value) = self {
-            params.insert(param_name.to_string(), value.to_string());
+            value.add_to_params(param_name, params);
         }
     }
 }
 
-// Implementations for common non-Option types
-/// TODO: We should use a better aproach to handle these types
-impl QueryParamValue for String {
-    fn add_to_params(
-        &self,
-        param_name: &str,
-        params: &mut std::collections::HashMap<String, String>,
-    ) {
-        params.insert(param_name.to_string(), self.clone());
+// Strings and primitives are sent using their `Display` form
+macro_rules! impl_query_param_value_display {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl QueryParamValue for $ty {
+                fn add_to_params(&self, param_name: &str, params: &mut QueryParams) {
+                    params.push((param_name.to_string(), self.to_string()));
+                }
+            }
+        )*
+    };
+}
+
+impl_query_param_value_display!(
+    String, &str, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, bool, char, f32,
+    f64,
+);
+
+// Implementation for Vec types, emitting one entry per element
+impl<T: std::fmt::Display> QueryParamValue for Vec<T> {
+    fn add_to_params(&self, param_name: &str, params: &mut QueryParams) {
+        for value in self {
+            params.push((param_name.to_string(), value.to_string()));
+        }
     }
 }
 
-impl QueryParamValue for &str {
-    fn add_to_params(
-        &self,
-        param_name: &str,
-        params: &mut std::collections::HashMap<String, String>,
-    ) {
-        params.insert(param_name.to_string(), self.to_string());
+// Durations are sent in seconds, with a fractional part only when needed
+impl QueryParamValue for std::time::Duration {
+    fn add_to_params(&self, param_name: &str, params: &mut QueryParams) {
+        let value = if self.subsec_nanos() == 0 {
+            self.as_secs().to_string()
+        } else {
+            self.as_secs_f64().to_string()
+        };
+        params.push((param_name.to_string(), value));
     }
 }
 
-impl QueryParamValue for u32 {
-    fn add_to_params(
-        &self,
-        param_name: &str,
-        params: &mut std::collections::HashMap<String, String>,
-    ) {
-        params.insert(param_name.to_string(), self.to_string());
+// Arrays and slices behave like Vec, one entry per element
+impl<T: std::fmt::Display, const N: usize> QueryParamValue for [T; N] {
+    fn add_to_params(&self, param_name: &str, params: &mut QueryParams) {
+        self.as_slice().add_to_params(param_name, params);
     }
 }
 
-impl QueryParamValue for u64 {
-    fn add_to_params(
-        &self,
-        param_name: &str,
-        params: &mut std::collections::HashMap<String, String>,
-    ) {
-        params.insert(param_name.to_string(), self.to_string());
+impl<T: std::fmt::Display> QueryParamValue for &[T] {
+    fn add_to_params(&self, param_name: &str, params: &mut QueryParams) {
+        for value in *self {
+            params.push((param_name.to_string(), value.to_string()));
+        }
     }
 }
 
-impl QueryParamValue for i32 {
-    fn add_to_params(
-        &self,
-        param_name: &str,
-        params: &mut std::collections::HashMap<String, String>,
-    ) {
-        params.insert(param_name.to_string(), self.to_string());
+// Dispatch used by the derive macro, so that any `Display` type works as a query
+// parameter without its own `QueryParamValue` impl. Method resolution tries
+// `QueryParamDispatch` before `&QueryParamDispatch`, so a `QueryParamValue` impl
+// (Option, Vec, Duration, ...) wins over the plain `Display` fallback.
+#[doc(hidden)]
+pub struct QueryParamDispatch<'a, T: ?Sized>(pub &'a T);
+
+#[doc(hidden)]
+pub trait QueryParamViaValue {
+    fn add_query_param(&self, param_name: &str, params: &mut QueryParams);
+}
+
+impl<T: QueryParamValue + ?Sized> QueryParamViaValue for QueryParamDispatch<'_, T> {
+    fn add_query_param(&self, param_name: &str, params: &mut QueryParams) {
+        self.0.add_to_params(param_name, params);
     }
 }
 
-impl QueryParamValue for i64 {
-    fn add_to_params(
-        &self,
-        param_name: &str,
-        params: &mut std::collections::HashMap<String, String>,
-    ) {
-        params.insert(param_name.to_string(), self.to_string());
+#[doc(hidden)]
+pub trait QueryParamViaDisplay {
+    fn add_query_param(&self, param_name: &str, params: &mut QueryParams);
+}
+
+impl<T: std::fmt::Display + ?Sized> QueryParamViaDisplay for &QueryParamDispatch<'_, T> {
+    fn add_query_param(&self, param_name: &str, params: &mut QueryParams) {
+        params.push((param_name.to_string(), self.0.to_string()));
     }
 }
 
-impl QueryParamValue for bool {
-    fn add_to_params(
-        &self,
-        param_name: &str,
-        params: &mut std::collections::HashMap<String, String>,
-    ) {
-        params.insert(param_name.to_string(), self.to_string());
+/// A list sent as a single comma-joined query parameter (`?ids=1,2,3`)
+///
+/// Borrows the elements of a `Vec<T>`, `[T; N]` or `&[T]`. An empty list adds
+/// no parameter, like an empty `Vec` does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommaSeparated<'a, T>(pub &'a [T]);
+
+impl<T: std::fmt::Display> QueryParamValue for CommaSeparated<'_, T> {
+    fn add_to_params(&self, param_name: &str, params: &mut QueryParams) {
+        if self.0.is_empty() {
+            return;
+        }
+        let joined = self
+            .0
+            .iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        params.push((param_name.to_string(), joined));
+    }
+}
+
+/// A group of query parameters that can be embedded in several requests
+///
+/// Fields marked `#[query(flatten)]` hoist these parameters into the request's
+/// query string, in the order returned.
+pub trait IntoQueryParams {
+    fn to_query_params(&self) -> QueryParams;
+}
+
+// An absent group adds no parameters
+impl<T: IntoQueryParams> IntoQueryParams for Option<T> {
+    fn to_query_params(&self) -> QueryParams {
+        self.as_ref()
+            .map(IntoQueryParams::to_query_params)
+            .unwrap_or_default()
+    }
+}
+
+// Date and time values are sent in their ISO 8601 / RFC 3339 form
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> QueryParamValue for chrono::DateTime<Tz>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    fn add_to_params(&self, param_name: &str, params: &mut QueryParams) {
+        params.push((param_name.to_string(), self.to_rfc3339()));
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl QueryParamValue for chrono::NaiveDate {
+    fn add_to_params(&self, param_name: &str, params: &mut QueryParams) {
+        params.push((param_name.to_string(), self.format("%Y-%m-%d").to_string()));
     }
 }
@@ -1,4 +1,5 @@
-use std::collections::HashMap;
+use crate::errors::ReqwestBuilderError;
+use serde::Serialize;
 
 /// Supported request body types
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -9,9 +10,268 @@ pub enum RequestBody {
     Form,
     /// Multipart form body (for file uploads, etc.)
     Multipart,
+    /// Plain-text body (`text/plain`)
+    Text,
+    /// Raw binary body (protobuf, images, etc.)
+    ///
+    /// No `Content-Type` is set; provide an appropriate one via `headers()`.
+    Bytes,
+    /// Newline-delimited JSON body (`application/x-ndjson`)
+    Ndjson,
+    /// GraphQL POST envelope, sent as JSON
+    GraphQL,
+    /// JSON Patch document (`application/json-patch+json`, RFC 6902)
+    JsonPatch,
+    /// XML body (`application/xml`)
+    Xml,
+    /// Streaming body, sent with chunked transfer encoding without buffering
+    ///
+    /// No `Content-Type` is set; provide an appropriate one via `headers()`.
+    Stream,
     /// No body (for GET, DELETE, etc.)
     None,
 }
 
+impl RequestBody {
+    /// `Content-Type` sent with this kind of body, unless `headers()` set one
+    ///
+    /// `None` for bodies without a fixed type: raw bytes and streams, whose
+    /// type depends on the payload, multipart forms, whose type carries a
+    /// generated boundary and is set by reqwest, and no body at all.
+    pub fn default_content_type(&self) -> Option<&'static str> {
+        match self {
+            RequestBody::Json | RequestBody::GraphQL => Some("application/json"),
+            RequestBody::Form => Some("application/x-www-form-urlencoded"),
+            RequestBody::Text => Some("text/plain"),
+            RequestBody::Ndjson => Some("application/x-ndjson"),
+            RequestBody::JsonPatch => Some("application/json-patch+json"),
+            RequestBody::Xml => Some("application/xml"),
+            RequestBody::Multipart
+            | RequestBody::Bytes
+            | RequestBody::Stream
+            | RequestBody::None => None,
+        }
+    }
+}
+
+/// How a form body sends array fields
+///
+/// With `Repeat`, `{"tags": ["a", "b"]}` is sent as `tags=a&tags=b`; with
+/// `Brackets` (as Rails and PHP expect) as `tags[]=a&tags[]=b`. `Json` sends
+/// the array as one JSON string, `tags=["a","b"]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FormArrayStyle {
+    /// One JSON-encoded value (the default)
+    #[default]
+    Json,
+    /// One `key=` entry per element
+    Repeat,
+    /// One `key[]=` entry per element
+    Brackets,
+}
+
 /// Query parameters for the request
-pub type QueryParams = HashMap<String, String>;
+///
+/// Kept in insertion order so URLs are stable, and the same key may appear
+/// more than once, producing repeated parameters such as `?tags=a&tags=b`.
+pub type QueryParams = Vec<(String, String)>;
+
+/// Standard GraphQL POST envelope
+///
+/// Serializes to `{ "query": ..., "variables": ..., "operationName": ... }`,
+/// omitting `variables` and `operationName` when not set.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GraphQLBody {
+    pub query: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variables: Option<serde_json::Value>,
+    #[serde(rename = "operationName", skip_serializing_if = "Option::is_none")]
+    pub operation_name: Option<String>,
+}
+
+impl GraphQLBody {
+    /// Create a new GraphQL body from a query document
+    pub fn new(query: impl Into<String>) -> Self {
+        Self {
+            query: query.into(),
+            variables: None,
+            operation_name: None,
+        }
+    }
+
+    /// Set the query variables
+    pub fn with_variables(mut self, variables: serde_json::Value) -> Self {
+        self.variables = Some(variables);
+        self
+    }
+
+    /// Set the operation name
+    pub fn with_operation_name(mut self, operation_name: impl Into<String>) -> Self {
+        self.operation_name = Some(operation_name.into());
+        self
+    }
+}
+
+/// JSON Patch document (RFC 6902)
+///
+/// Serializes to an array of operations, applied by the server in order:
+///
+/// ```
+/// use reqwest_builder::JsonPatch;
+///
+/// let patch = JsonPatch::new()
+///     .replace("/name", "Ada")
+///     .remove("/nickname");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct JsonPatch(pub Vec<PatchOperation>);
+
+/// A single JSON Patch operation, tagged by `op`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    Add {
+        path: String,
+        value: serde_json::Value,
+    },
+    Remove {
+        path: String,
+    },
+    Replace {
+        path: String,
+        value: serde_json::Value,
+    },
+    Move {
+        from: String,
+        path: String,
+    },
+    Copy {
+        from: String,
+        path: String,
+    },
+    Test {
+        path: String,
+        value: serde_json::Value,
+    },
+}
+
+impl JsonPatch {
+    /// Create an empty patch
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `value` at `path`
+    pub fn add(self, path: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        self.with(PatchOperation::Add {
+            path: path.into(),
+            value: value.into(),
+        })
+    }
+
+    /// Remove the value at `path`
+    pub fn remove(self, path: impl Into<String>) -> Self {
+        self.with(PatchOperation::Remove { path: path.into() })
+    }
+
+    /// Replace the value at `path` with `value`
+    pub fn replace(self, path: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        self.with(PatchOperation::Replace {
+            path: path.into(),
+            value: value.into(),
+        })
+    }
+
+    /// Move the value at `from` to `path`
+    pub fn move_from(self, from: impl Into<String>, path: impl Into<String>) -> Self {
+        self.with(PatchOperation::Move {
+            from: from.into(),
+            path: path.into(),
+        })
+    }
+
+    /// Copy the value at `from` to `path`
+    pub fn copy_from(self, from: impl Into<String>, path: impl Into<String>) -> Self {
+        self.with(PatchOperation::Copy {
+            from: from.into(),
+            path: path.into(),
+        })
+    }
+
+    /// Require the value at `path` to equal `value` for the patch to apply
+    pub fn test(self, path: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        self.with(PatchOperation::Test {
+            path: path.into(),
+            value: value.into(),
+        })
+    }
+
+    /// Append an operation
+    pub fn with(mut self, operation: PatchOperation) -> Self {
+        self.0.push(operation);
+        self
+    }
+}
+
+/// Cookies sent together in a single `Cookie` header
+///
+/// Pairs are rendered in order as `name=value; name=value`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cookies(pub Vec<(String, String)>);
+
+impl Cookies {
+    /// Create an empty cookie list
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a cookie
+    pub fn with(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.0.push((name.into(), value.into()));
+        self
+    }
+
+    /// Render the `Cookie` header value, validating names and values
+    ///
+    /// Names must be HTTP tokens and values RFC 6265 cookie octets, optionally
+    /// wrapped in double quotes; anything else (e.g. `;`, `,` or spaces) is a
+    /// `HeaderError`. Percent-encode values up front if they need those characters.
+    pub fn header_value(&self) -> std::result::Result<String, ReqwestBuilderError> {
+        let mut pairs = Vec::with_capacity(self.0.len());
+
+        for (name, value) in &self.0 {
+            let invalid = |reason: &str| ReqwestBuilderError::HeaderError {
+                key: "Cookie".to_string(),
+                value: format!("{}={}", name, value),
+                source: reason.to_string(),
+            };
+
+            if name.is_empty() || !name.bytes().all(is_token_byte) {
+                return Err(invalid("Invalid cookie name"));
+            }
+
+            let unquoted = value
+                .strip_prefix('"')
+                .and_then(|rest| rest.strip_suffix('"'))
+                .unwrap_or(value);
+            if !unquoted.bytes().all(is_cookie_octet) {
+                return Err(invalid("Invalid cookie value"));
+            }
+
+            pairs.push(format!("{}={}", name, value));
+        }
+
+        Ok(pairs.join("; "))
+    }
+}
+
+/// RFC 9110 `tchar`
+fn is_token_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&byte)
+}
+
+/// RFC 6265 `cookie-octet`: visible ASCII except `"`, `,`, `;` and `\`
+fn is_cookie_octet(byte: u8) -> bool {
+    matches!(byte, 0x21 | 0x23..=0x2B | 0x2D..=0x3A | 0x3C..=0x5B | 0x5D..=0x7E)
+}
@@ -0,0 +1,75 @@
+// Requests defined using nothing but the prelude (and serde)
+use reqwest_builder::prelude::*;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct UploadAvatarRequest {
+    #[serde(skip)]
+    avatar: FileUpload,
+}
+
+impl IntoReqwestBuilder for UploadAvatarRequest {
+    type Headers = ();
+
+    fn method(&self) -> http::Method {
+        http::Method::PUT
+    }
+
+    fn endpoint(&self) -> String {
+        "/me/avatar".to_string()
+    }
+
+    fn body(&self) -> RequestBody {
+        RequestBody::Multipart
+    }
+
+    fn query_params(&self) -> Option<QueryParams> {
+        Some(vec![("resize".to_string(), "true".to_string())])
+    }
+
+    fn multipart_files(&self) -> Vec<&FileUpload> {
+        vec![&self.avatar]
+    }
+
+    fn create_multipart_form(&self) -> Option<reqwest::multipart::Form> {
+        Some(reqwest::multipart::Form::new().part("avatar", self.avatar.to_multipart_part()))
+    }
+}
+
+#[test]
+fn test_prelude_manual_impl() -> Result<(), ReqwestBuilderError> {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = url::Url::parse("https://api.example.com").unwrap();
+    let request = UploadAvatarRequest {
+        avatar: FileUpload::from_bytes("avatar.png".to_string(), vec![1, 2, 3], None),
+    };
+
+    let built = request.into_reqwest_builder(&client, &base_url)?.build()?;
+    assert_eq!(built.method(), http::Method::PUT);
+    assert_eq!(
+        built.url().as_str(),
+        "https://api.example.com/me/avatar?resize=true"
+    );
+    Ok(())
+}
+
+#[cfg(feature = "derive")]
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/users/{id}", body = "none")]
+struct GetUserRequest {
+    #[path_param]
+    id: u64,
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_prelude_derive() -> Result<(), ReqwestBuilderError> {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = url::Url::parse("https://api.example.com").unwrap();
+
+    let built = GetUserRequest { id: 7 }
+        .into_reqwest_builder(&client, &base_url)?
+        .build()?;
+    assert_eq!(built.url().as_str(), "https://api.example.com/users/7");
+    Ok(())
+}
@@ -1,10 +1,14 @@
 use reqwest_builder::{
-    construct_url,
+    construct_url, construct_url_typed,
     errors::ReqwestBuilderError,
     file_upload::FileUpload,
-    serialization::{serialize_to_form_params, serialize_to_header_map},
+    has_json_body_content, query_param_helper,
+    serialization::{
+        serialize_to_form_pairs, serialize_to_form_params, serialize_to_form_params_nested,
+        serialize_to_header_map,
+    },
     trait_impl::IntoReqwestBuilder,
-    types::RequestBody,
+    types::{FormArrayStyle, QueryParams, RequestBody},
 };
 use serde::Serialize;
 use url::Url;
@@ -43,6 +47,81 @@ fn test_construct_url() {
     assert_eq!(result, "https://api.example.com");
 }
 
+#[test]
+fn test_construct_url_typed() {
+    let base_url = Url::parse("https://api.example.com/v2?key=abc").unwrap();
+    let url = construct_url_typed(&base_url, "/users/1?expand=posts").unwrap();
+    assert_eq!(url.path(), "/v2/users/1");
+    assert_eq!(url.query(), Some("key=abc&expand=posts"));
+
+    let next = construct_url_typed(&base_url, "https://other.host/x?cursor=abc").unwrap();
+    assert_eq!(next.as_str(), "https://other.host/x?cursor=abc");
+
+    // A cannot-be-a-base URL can't have an endpoint joined onto it
+    let mailto = Url::parse("mailto:team@example.com").unwrap();
+    assert!(matches!(
+        construct_url_typed(&mailto, "/users"),
+        Err(ReqwestBuilderError::UrlError(_))
+    ));
+}
+
+#[test]
+fn test_construct_url_with_base_path_prefix() {
+    let base_url = Url::parse("https://api.example.com/v2").unwrap();
+    assert_eq!(
+        construct_url(&base_url, "/users"),
+        "https://api.example.com/v2/users"
+    );
+
+    let base_url = Url::parse("https://api.example.com/v2/").unwrap();
+    assert_eq!(
+        construct_url(&base_url, "/users"),
+        "https://api.example.com/v2/users"
+    );
+
+    let base_url = Url::parse("https://api.example.com/api/v2/").unwrap();
+    assert_eq!(
+        construct_url(&base_url, "users/1/posts"),
+        "https://api.example.com/api/v2/users/1/posts"
+    );
+    assert_eq!(
+        construct_url(&base_url, ""),
+        "https://api.example.com/api/v2"
+    );
+}
+
+#[test]
+fn test_construct_url_with_absolute_endpoint() {
+    let base_url = Url::parse("https://api.example.com/v2?api_key=abc").unwrap();
+    assert_eq!(
+        construct_url(&base_url, "https://other.host/x?cursor=abc"),
+        "https://other.host/x?cursor=abc"
+    );
+
+    // Relative endpoints, including ones with a colon, are still joined
+    assert_eq!(
+        construct_url(&base_url, "/users:search"),
+        "https://api.example.com/v2/users:search?api_key=abc"
+    );
+}
+
+#[test]
+fn test_construct_url_with_base_query_string() {
+    let base_url = Url::parse("https://api.example.com/v2?api_key=abc").unwrap();
+    assert_eq!(
+        construct_url(&base_url, "/users"),
+        "https://api.example.com/v2/users?api_key=abc"
+    );
+    assert_eq!(
+        construct_url(&base_url, "/users?page=2"),
+        "https://api.example.com/v2/users?api_key=abc&page=2"
+    );
+    assert_eq!(
+        construct_url(&base_url, ""),
+        "https://api.example.com/v2?api_key=abc"
+    );
+}
+
 #[test]
 fn test_request_body_none() {
     #[derive(Serialize)]
@@ -120,6 +199,56 @@ fn test_serialize_to_header_map_invalid_header() {
     }
 }
 
+#[test]
+fn test_serialize_to_header_map_multi_value() {
+    #[derive(Serialize)]
+    struct CookieHeaders {
+        #[serde(rename = "Cookie")]
+        cookies: Vec<String>,
+        #[serde(rename = "X-Request-Id")]
+        request_id: String,
+    }
+
+    let headers = CookieHeaders {
+        cookies: vec!["a=1".to_string(), "b=2".to_string()],
+        request_id: "abc".to_string(),
+    };
+
+    let header_map = serialize_to_header_map(&headers).unwrap();
+    let cookies: Vec<_> = header_map.get_all("Cookie").iter().collect();
+    assert_eq!(cookies, vec!["a=1", "b=2"]);
+    assert_eq!(header_map.get_all("X-Request-Id").iter().count(), 1);
+}
+
+#[test]
+fn test_serialize_header_map_byte_values() {
+    #[derive(Serialize)]
+    struct SignedHeaders {
+        #[serde(rename = "X-Signature")]
+        signature: Vec<u8>,
+        #[serde(rename = "X-Tag")]
+        tags: Vec<String>,
+    }
+
+    let headers = SignedHeaders {
+        // "café" in latin-1, which isn't valid UTF-8
+        signature: vec![b'c', b'a', b'f', 0xE9],
+        tags: vec!["a".to_string(), "b".to_string()],
+    };
+
+    let header_map = serialize_to_header_map(&headers).unwrap();
+    let signature = header_map.get("X-Signature").unwrap();
+    assert_eq!(signature.as_bytes(), &[b'c', b'a', b'f', 0xE9]);
+    assert!(signature.to_str().is_err());
+    assert_eq!(header_map.get_all("X-Tag").iter().count(), 2);
+
+    let invalid = serialize_to_header_map(&serde_json::json!({ "X-Bad": [10] }));
+    assert!(matches!(
+        invalid,
+        Err(ReqwestBuilderError::HeaderError { ref key, .. }) if key == "X-Bad"
+    ));
+}
+
 #[test]
 fn test_serialize_to_form_params_with_error_handling() {
     let test_data = TestRequest {
@@ -136,13 +265,110 @@ fn test_serialize_to_form_params_with_error_handling() {
     assert_eq!(params.get("field3"), Some(&"value3".to_string()));
 }
 
+#[test]
+fn test_serialize_to_form_params_numeric_precision() {
+    #[derive(Serialize)]
+    struct Numbers {
+        big: i64,
+        small: i64,
+        unsigned: u64,
+        whole: f64,
+        fraction: f64,
+    }
+
+    let params = serialize_to_form_params(&Numbers {
+        big: i64::MAX,
+        small: i64::MIN,
+        unsigned: u64::MAX,
+        whole: 1.0,
+        fraction: 0.1,
+    })
+    .unwrap();
+
+    assert_eq!(params["big"], "9223372036854775807");
+    assert_eq!(params["small"], "-9223372036854775808");
+    assert_eq!(params["unsigned"], "18446744073709551615");
+    assert_eq!(params["whole"], "1.0");
+    assert_eq!(params["fraction"], "0.1");
+
+    let mut query = Vec::new();
+    query_param_helper(&i64::MAX, "big", &mut query);
+    query_param_helper(&1.0_f64, "whole", &mut query);
+    assert_eq!(
+        query,
+        vec![
+            ("big".to_string(), "9223372036854775807".to_string()),
+            ("whole".to_string(), "1".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_serialize_to_form_params_nested_object() {
+    let data = serde_json::json!({
+        "user": { "name": "x", "address": { "city": "Berlin" }, "nickname": null },
+        "active": true,
+    });
+
+    let params = serialize_to_form_params_nested(&data).unwrap();
+    let mut pairs: Vec<_> = params.iter().collect();
+    pairs.sort();
+    assert_eq!(
+        pairs,
+        vec![
+            (&"active".to_string(), &"true".to_string()),
+            (&"user[address][city]".to_string(), &"Berlin".to_string()),
+            (&"user[name]".to_string(), &"x".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_serialize_to_form_params_nested_array() {
+    let data = serde_json::json!({
+        "tags": ["a", "b"],
+        "items": [{ "id": 1 }, { "id": 2 }],
+    });
+
+    let params = serialize_to_form_params_nested(&data).unwrap();
+    assert_eq!(params.len(), 4);
+    assert_eq!(params["tags[0]"], "a");
+    assert_eq!(params["tags[1]"], "b");
+    assert_eq!(params["items[0][id]"], "1");
+    assert_eq!(params["items[1][id]"], "2");
+}
+
+#[test]
+fn test_serialize_to_form_pairs_array_styles() {
+    let data = serde_json::json!({
+        "tags": ["a", null, "b"],
+        "name": "x",
+    });
+
+    assert_eq!(
+        serialize_to_form_pairs(&data, FormArrayStyle::Repeat).unwrap(),
+        vec![
+            ("name".to_string(), "x".to_string()),
+            ("tags".to_string(), "a".to_string()),
+            ("tags".to_string(), "b".to_string()),
+        ]
+    );
+    assert_eq!(
+        serialize_to_form_pairs(&data, FormArrayStyle::Json).unwrap(),
+        vec![
+            ("name".to_string(), "x".to_string()),
+            ("tags".to_string(), r#"["a",null,"b"]"#.to_string()),
+        ]
+    );
+}
+
 #[test]
 fn test_file_upload_error_handling() {
     // Test with non-existent file
     let result = FileUpload::from_path("/non/existent/file.txt");
     assert!(result.is_err());
     match result.unwrap_err() {
-        ReqwestBuilderError::IoError(_) => {
+        ReqwestBuilderError::IoError { .. } => {
             // Expected
         }
         _ => panic!("Expected IoError"),
@@ -163,3 +389,2572 @@ fn test_into_reqwest_builder() {
     let result = request.into_reqwest_builder(&client, &base_url);
     assert!(result.is_ok());
 }
+
+#[test]
+fn test_query_param_helper_floats() {
+    let mut params = Vec::new();
+
+    query_param_helper(&19.99_f64, "price", &mut params);
+    query_param_helper(&20.0_f64, "whole", &mut params);
+    query_param_helper(&0.5_f32, "ratio", &mut params);
+    query_param_helper(&Some(3.0_f32), "optional", &mut params);
+
+    assert_eq!(
+        params,
+        vec![
+            ("price".to_string(), "19.99".to_string()),
+            ("whole".to_string(), "20".to_string()),
+            ("ratio".to_string(), "0.5".to_string()),
+            ("optional".to_string(), "3".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_json_body_skipped_when_all_fields_none() {
+    #[derive(Serialize)]
+    struct OptionalRequest {
+        name: Option<String>,
+        age: Option<u32>,
+    }
+
+    impl IntoReqwestBuilder for OptionalRequest {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::PATCH
+        }
+
+        fn endpoint(&self) -> String {
+            "/users/1".to_string()
+        }
+    }
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let empty = OptionalRequest {
+        name: None,
+        age: None,
+    };
+    let built = empty
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert!(built.body().is_none());
+
+    let partial = OptionalRequest {
+        name: Some("John".to_string()),
+        age: None,
+    };
+    let built = partial
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert!(built.body().is_some());
+}
+
+#[test]
+fn test_has_json_body_content() {
+    assert!(!has_json_body_content(&serde_json::json!({})));
+    assert!(!has_json_body_content(&serde_json::json!({ "a": null })));
+    assert!(!has_json_body_content(
+        &serde_json::from_str("{ \n  \"a\" : null ,\t\"b\": null }").unwrap()
+    ));
+    assert!(has_json_body_content(&serde_json::json!({ "a": " " })));
+    assert!(has_json_body_content(&serde_json::json!([])));
+}
+
+#[test]
+fn test_text_body() {
+    #[derive(Serialize)]
+    struct MarkdownRequest {
+        document: String,
+    }
+
+    impl IntoReqwestBuilder for MarkdownRequest {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::POST
+        }
+
+        fn endpoint(&self) -> String {
+            "/render".to_string()
+        }
+
+        fn body(&self) -> RequestBody {
+            RequestBody::Text
+        }
+
+        fn text_body(&self) -> Option<String> {
+            Some(self.document.clone())
+        }
+    }
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = MarkdownRequest {
+        document: "# Title".to_string(),
+    };
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(built.headers().get("Content-Type").unwrap(), "text/plain");
+    assert_eq!(built.body().unwrap().as_bytes(), Some("# Title".as_bytes()));
+}
+
+#[test]
+fn test_bytes_body() {
+    #[derive(Serialize)]
+    struct UploadRequest {
+        payload: Vec<u8>,
+    }
+
+    impl IntoReqwestBuilder for UploadRequest {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::POST
+        }
+
+        fn endpoint(&self) -> String {
+            "/upload".to_string()
+        }
+
+        fn body(&self) -> RequestBody {
+            RequestBody::Bytes
+        }
+
+        fn bytes_body(&self) -> Option<Vec<u8>> {
+            Some(self.payload.clone())
+        }
+    }
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let payload = vec![0x08, 0x96, 0x01, 0x00, 0xff];
+    let request = UploadRequest {
+        payload: payload.clone(),
+    };
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(built.body().unwrap().as_bytes(), Some(payload.as_slice()));
+    assert!(built.headers().get("Content-Type").is_none());
+}
+
+#[test]
+fn test_dyn_requests_in_one_collection() {
+    use reqwest_builder::DynIntoReqwestBuilder;
+
+    #[derive(Serialize)]
+    struct ListUsers {
+        page: u32,
+    }
+
+    impl IntoReqwestBuilder for ListUsers {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::GET
+        }
+
+        fn endpoint(&self) -> String {
+            "/users".to_string()
+        }
+
+        fn query_params(&self) -> Option<QueryParams> {
+            Some(vec![("page".to_string(), self.page.to_string())])
+        }
+
+        fn body(&self) -> RequestBody {
+            RequestBody::None
+        }
+    }
+
+    let requests: Vec<Box<dyn DynIntoReqwestBuilder>> = vec![
+        Box::new(ListUsers { page: 2 }),
+        Box::new(TestRequest {
+            field1: "value1".to_string(),
+            field2: 42,
+            field3: None,
+        }),
+    ];
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+    let built: Vec<_> = requests
+        .iter()
+        .map(|request| {
+            request
+                .erased_build_request(&client, &base_url)
+                .unwrap()
+                .build()
+                .unwrap()
+        })
+        .collect();
+
+    assert_eq!(built[0].method(), http::Method::GET);
+    assert_eq!(
+        built[0].url().as_str(),
+        "https://api.example.com/users?page=2"
+    );
+    assert!(built[0].body().is_none());
+
+    assert_eq!(requests[1].erased_method(), http::Method::POST);
+    assert_eq!(requests[1].erased_endpoint(), "/test/endpoint");
+    assert_eq!(
+        requests[1].erased_body().unwrap(),
+        serde_json::json!({ "field1": "value1", "field2": 42, "field3": null })
+    );
+    assert_eq!(
+        built[1].url().as_str(),
+        "https://api.example.com/test/endpoint"
+    );
+    assert!(built[1].body().is_some());
+}
+
+#[test]
+fn test_json_value_body_replaces_serialized_struct() {
+    #[derive(Serialize)]
+    struct DynamicPatch {
+        #[serde(skip)]
+        fields: Vec<(&'static str, serde_json::Value)>,
+    }
+
+    impl IntoReqwestBuilder for DynamicPatch {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::PATCH
+        }
+
+        fn endpoint(&self) -> String {
+            "/settings".to_string()
+        }
+
+        fn json_value_body(&self) -> Option<serde_json::Value> {
+            let map = self
+                .fields
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.clone()))
+                .collect();
+            Some(serde_json::Value::Object(map))
+        }
+    }
+
+    let request = DynamicPatch {
+        fields: vec![
+            ("theme", serde_json::json!("dark")),
+            ("limits", serde_json::json!([1, 2])),
+        ],
+    };
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        built.headers().get("content-type").unwrap(),
+        "application/json"
+    );
+    let body: serde_json::Value =
+        serde_json::from_slice(built.body().unwrap().as_bytes().unwrap()).unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({ "theme": "dark", "limits": [1, 2] })
+    );
+}
+
+#[test]
+fn test_sign_hook_adds_hmac_signature() {
+    use hmac::{Hmac, Mac};
+
+    fn hmac_hex(message: &[u8]) -> String {
+        let mut mac = Hmac::<sha2::Sha256>::new_from_slice(b"secret-key").unwrap();
+        mac.update(message);
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    #[derive(Serialize)]
+    struct CreateOrder {
+        #[serde(skip)]
+        account: String,
+        symbol: String,
+        quantity: u32,
+    }
+
+    impl IntoReqwestBuilder for CreateOrder {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::POST
+        }
+
+        fn endpoint(&self) -> String {
+            "/orders".to_string()
+        }
+
+        fn query_params(&self) -> Option<QueryParams> {
+            Some(vec![("account".to_string(), self.account.clone())])
+        }
+
+        fn sign(&self, method: &http::Method, url: &Url, body: &[u8]) -> Option<(String, String)> {
+            let mut message = format!("{}\n{}?{}\n", method, url.path(), url.query()?).into_bytes();
+            message.extend_from_slice(body);
+            Some(("X-Signature".to_string(), hmac_hex(&message)))
+        }
+    }
+
+    let request = CreateOrder {
+        account: "acc-1".to_string(),
+        symbol: "BTC".to_string(),
+        quantity: 2,
+    };
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let body = br#"{"quantity":2,"symbol":"BTC"}"#;
+    assert_eq!(built.body().unwrap().as_bytes().unwrap(), body);
+
+    let expected = hmac_hex(&[b"POST\n/orders?account=acc-1\n".as_slice(), body].concat());
+    assert_eq!(
+        built.headers().get("x-signature").unwrap(),
+        expected.as_str()
+    );
+}
+
+#[test]
+fn test_sign_hook_signs_multipart_request_over_empty_body() {
+    #[derive(Serialize)]
+    struct UploadAvatar;
+
+    impl IntoReqwestBuilder for UploadAvatar {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::POST
+        }
+
+        fn endpoint(&self) -> String {
+            "/avatar".to_string()
+        }
+
+        fn body(&self) -> RequestBody {
+            RequestBody::Multipart
+        }
+
+        fn create_multipart_form(&self) -> Option<reqwest::multipart::Form> {
+            Some(reqwest::multipart::Form::new().text("name", "ada"))
+        }
+
+        fn sign(&self, method: &http::Method, url: &Url, body: &[u8]) -> Option<(String, String)> {
+            Some((
+                "X-Signature".to_string(),
+                format!("{} {} {}", method, url.path(), body.len()),
+            ))
+        }
+    }
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+    let built = UploadAvatar
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        built.headers().get("x-signature").unwrap(),
+        "POST /avatar 0"
+    );
+}
+
+#[test]
+fn test_next_page_token_from_link_header() {
+    use reqwest_builder::{link_relation, next_page_token};
+
+    let mut headers = http::HeaderMap::new();
+    headers.insert(
+        http::header::LINK,
+        http::HeaderValue::from_static(
+            "<https://api.example.com/issues?page=1>; rel=\"prev\", \
+             <https://api.example.com/issues?page=3&sort=a,b>; rel=\"next\", \
+             <https://api.example.com/issues?page=9>; rel=\"last\"",
+        ),
+    );
+    assert_eq!(
+        next_page_token(&headers),
+        Some("https://api.example.com/issues?page=3&sort=a,b".to_string())
+    );
+    assert_eq!(
+        link_relation(&headers, "last"),
+        Some("https://api.example.com/issues?page=9".to_string())
+    );
+
+    // Without a next link, a cursor header is used; the last page has neither
+    let mut headers = http::HeaderMap::new();
+    headers.insert("x-next-cursor", http::HeaderValue::from_static("abc123"));
+    assert_eq!(next_page_token(&headers), Some("abc123".to_string()));
+    assert_eq!(next_page_token(&http::HeaderMap::new()), None);
+}
+
+#[test]
+fn test_embed_query_in_path() {
+    #[derive(Serialize)]
+    struct SearchRequest {
+        embed: bool,
+    }
+
+    impl IntoReqwestBuilder for SearchRequest {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::GET
+        }
+
+        fn endpoint(&self) -> String {
+            "/search?v=2".to_string()
+        }
+
+        fn query_params(&self) -> Option<QueryParams> {
+            Some(vec![
+                ("q".to_string(), "rust lang".to_string()),
+                ("tag".to_string(), "a+b".to_string()),
+            ])
+        }
+
+        fn body(&self) -> RequestBody {
+            RequestBody::None
+        }
+
+        fn embed_query_in_path(&self) -> bool {
+            self.embed
+        }
+    }
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com/api").unwrap();
+    let url_of = |request: SearchRequest| {
+        request
+            .into_reqwest_builder(&client, &base_url)
+            .unwrap()
+            .build()
+            .unwrap()
+            .url()
+            .to_string()
+    };
+
+    // Rendered into the endpoint with RFC 3986 encoding before the URL is parsed
+    assert_eq!(
+        url_of(SearchRequest { embed: true }),
+        "https://api.example.com/api/search?v=2&q=rust%20lang&tag=a%2Bb"
+    );
+    // Form-encoded onto the parsed URL by default
+    assert_eq!(
+        url_of(SearchRequest { embed: false }),
+        "https://api.example.com/api/search?v=2&q=rust+lang&tag=a%2Bb"
+    );
+}
+
+#[test]
+fn test_default_content_types() {
+    assert_eq!(
+        RequestBody::Json.default_content_type(),
+        Some("application/json")
+    );
+    assert_eq!(
+        RequestBody::Form.default_content_type(),
+        Some("application/x-www-form-urlencoded")
+    );
+    assert_eq!(RequestBody::Text.default_content_type(), Some("text/plain"));
+    assert_eq!(
+        RequestBody::Ndjson.default_content_type(),
+        Some("application/x-ndjson")
+    );
+    assert_eq!(
+        RequestBody::GraphQL.default_content_type(),
+        Some("application/json")
+    );
+    assert_eq!(
+        RequestBody::JsonPatch.default_content_type(),
+        Some("application/json-patch+json")
+    );
+    assert_eq!(
+        RequestBody::Xml.default_content_type(),
+        Some("application/xml")
+    );
+
+    // The type depends on the payload, or carries a generated boundary
+    assert_eq!(RequestBody::Multipart.default_content_type(), None);
+    assert_eq!(RequestBody::Bytes.default_content_type(), None);
+    assert_eq!(RequestBody::Stream.default_content_type(), None);
+    assert_eq!(RequestBody::None.default_content_type(), None);
+}
+
+#[tokio::test]
+async fn test_send_reaches_server() {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/test/endpoint"))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let request = TestRequest {
+        field1: "value1".to_string(),
+        field2: 42,
+        field3: None,
+    };
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse(&server.uri()).unwrap();
+
+    let response = request.send(&client, &base_url).await.unwrap();
+    assert_eq!(response.status(), 201);
+}
+
+#[tokio::test]
+async fn test_send_transport_error() {
+    let request = TestRequest {
+        field1: "value1".to_string(),
+        field2: 42,
+        field3: None,
+    };
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("http://127.0.0.1:1").unwrap();
+
+    match request.send(&client, &base_url).await {
+        Err(ReqwestBuilderError::RequestError(_)) => {}
+        other => panic!("Expected RequestError, got {:?}", other.map(|r| r.status())),
+    }
+}
+
+#[derive(Serialize)]
+struct SlowRequest;
+
+impl IntoReqwestBuilder for SlowRequest {
+    type Headers = ();
+
+    fn method(&self) -> http::Method {
+        http::Method::GET
+    }
+
+    fn endpoint(&self) -> String {
+        "/slow".to_string()
+    }
+
+    fn timeout(&self) -> Option<std::time::Duration> {
+        Some(std::time::Duration::from_millis(50))
+    }
+
+    fn body(&self) -> RequestBody {
+        RequestBody::None
+    }
+}
+
+#[test]
+fn test_timeout_applied_to_builder() {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let built = SlowRequest
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(built.timeout(), Some(&std::time::Duration::from_millis(50)));
+}
+
+#[tokio::test]
+async fn test_send_timeout_error() {
+    use wiremock::matchers::path;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(path("/slow"))
+        .respond_with(ResponseTemplate::new(200).set_delay(std::time::Duration::from_millis(500)))
+        .mount(&server)
+        .await;
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse(&server.uri()).unwrap();
+
+    match SlowRequest.send(&client, &base_url).await {
+        Err(ReqwestBuilderError::Timeout(_)) => {}
+        other => panic!("Expected Timeout, got {:?}", other.map(|r| r.status())),
+    }
+}
+
+#[tokio::test]
+async fn test_send_json_deserializes_response() {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct CreatedResponse {
+        id: u64,
+        name: String,
+    }
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/test/endpoint"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "id": 1, "name": "value1" })),
+        )
+        .mount(&server)
+        .await;
+
+    let request = TestRequest {
+        field1: "value1".to_string(),
+        field2: 42,
+        field3: None,
+    };
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse(&server.uri()).unwrap();
+
+    let response: CreatedResponse = request.send_json(&client, &base_url).await.unwrap();
+    assert_eq!(
+        response,
+        CreatedResponse {
+            id: 1,
+            name: "value1".to_string(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_send_parsed_success_and_api_error() {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct CreatedResponse {
+        id: u64,
+    }
+
+    #[derive(serde::Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct ApiFailure {
+        code: String,
+        message: String,
+    }
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/test/endpoint"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({ "id": 7 })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/rejected/test/endpoint"))
+        .respond_with(ResponseTemplate::new(422).set_body_json(
+            serde_json::json!({ "code": "invalid_name", "message": "name is taken" }),
+        ))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/broken/test/endpoint"))
+        .respond_with(ResponseTemplate::new(502).set_body_string("bad gateway"))
+        .mount(&server)
+        .await;
+
+    let request = || TestRequest {
+        field1: "value1".to_string(),
+        field2: 42,
+        field3: None,
+    };
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+
+    let base_url = Url::parse(&server.uri()).unwrap();
+    let created = request()
+        .send_parsed::<CreatedResponse, ApiFailure>(&client, &base_url)
+        .await
+        .unwrap();
+    assert_eq!(created, CreatedResponse { id: 7 });
+
+    let base_url = Url::parse(&format!("{}/rejected/", server.uri())).unwrap();
+    let err = request()
+        .send_parsed::<CreatedResponse, ApiFailure>(&client, &base_url)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ReqwestBuilderError::ApiError {
+            status: 422,
+            error: r#"ApiFailure { code: "invalid_name", message: "name is taken" }"#.to_string(),
+        }
+    );
+
+    // A body that isn't the API's error type is kept as text
+    let base_url = Url::parse(&format!("{}/broken/", server.uri())).unwrap();
+    let err = request()
+        .send_parsed::<CreatedResponse, ApiFailure>(&client, &base_url)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ReqwestBuilderError::ResponseError {
+            status: 502,
+            body: "bad gateway".to_string(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_send_json_error_status() {
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("boom"))
+        .mount(&server)
+        .await;
+
+    let request = TestRequest {
+        field1: "value1".to_string(),
+        field2: 42,
+        field3: None,
+    };
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse(&server.uri()).unwrap();
+
+    let result = request
+        .send_json::<serde_json::Value>(&client, &base_url)
+        .await;
+    assert_eq!(
+        result.unwrap_err(),
+        ReqwestBuilderError::ResponseError {
+            status: 500,
+            body: "boom".to_string(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_handle_response_success_and_error() {
+    use reqwest_builder::handle_response;
+    use wiremock::matchers::path;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct Item {
+        id: u64,
+    }
+
+    let server = MockServer::start().await;
+    Mock::given(path("/items/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": 1 })))
+        .mount(&server)
+        .await;
+    Mock::given(path("/items/2"))
+        .respond_with(ResponseTemplate::new(404).set_body_string(r#"{"error":"not found"}"#))
+        .mount(&server)
+        .await;
+
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get(format!("{}/items/1", server.uri()))
+        .send()
+        .await
+        .unwrap();
+    let item: Item = handle_response(response).await.unwrap();
+    assert_eq!(item, Item { id: 1 });
+
+    let response = client
+        .get(format!("{}/items/2", server.uri()))
+        .send()
+        .await
+        .unwrap();
+    let result = handle_response::<Item>(response).await;
+    assert_eq!(
+        result.unwrap_err(),
+        ReqwestBuilderError::ResponseError {
+            status: 404,
+            body: r#"{"error":"not found"}"#.to_string(),
+        }
+    );
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_send_with_retry_recovers_from_server_errors() {
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(2)
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let request = TestRequest {
+        field1: "value1".to_string(),
+        field2: 42,
+        field3: None,
+    };
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse(&server.uri()).unwrap();
+
+    let response = request
+        .send_with_retry(&client, &base_url, 3)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+}
+
+#[test]
+fn test_to_http_request() {
+    #[derive(Serialize)]
+    struct LoginRequest {
+        username: String,
+    }
+
+    impl IntoReqwestBuilder for LoginRequest {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::POST
+        }
+
+        fn endpoint(&self) -> String {
+            "/login".to_string()
+        }
+
+        fn body(&self) -> RequestBody {
+            RequestBody::Form
+        }
+    }
+
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = TestRequest {
+        field1: "value1".to_string(),
+        field2: 42,
+        field3: None,
+    };
+    let http_request = request.to_http_request(&base_url).unwrap();
+    assert_eq!(http_request.method(), http::Method::POST);
+    assert_eq!(http_request.uri(), "https://api.example.com/test/endpoint");
+    assert_eq!(
+        http_request.headers().get("Content-Type").unwrap(),
+        "application/json"
+    );
+    let body: serde_json::Value = serde_json::from_slice(http_request.body()).unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({ "field1": "value1", "field2": 42, "field3": null })
+    );
+
+    let login = LoginRequest {
+        username: "alice".to_string(),
+    };
+    let http_request = login.to_http_request(&base_url).unwrap();
+    assert_eq!(http_request.uri(), "https://api.example.com/login");
+    assert_eq!(
+        http_request.headers().get("Content-Type").unwrap(),
+        "application/x-www-form-urlencoded"
+    );
+    assert_eq!(http_request.body(), b"username=alice");
+}
+
+#[test]
+fn test_cookies_header() {
+    use reqwest_builder::Cookies;
+
+    #[derive(Serialize)]
+    struct SessionRequest;
+
+    impl IntoReqwestBuilder for SessionRequest {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::GET
+        }
+
+        fn endpoint(&self) -> String {
+            "/session".to_string()
+        }
+
+        fn cookies(&self) -> Option<Cookies> {
+            Some(Cookies::new().with("a", "1").with("session", "\"abc\""))
+        }
+
+        fn body(&self) -> RequestBody {
+            RequestBody::None
+        }
+    }
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let built = SessionRequest
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(
+        built.headers().get("Cookie").unwrap(),
+        "a=1; session=\"abc\""
+    );
+
+    let invalid = Cookies::new().with("a", "1; b=2").header_value();
+    assert!(matches!(
+        invalid,
+        Err(ReqwestBuilderError::HeaderError { source, .. }) if source == "Invalid cookie value"
+    ));
+    assert!(Cookies::new().with("bad name", "1").header_value().is_err());
+}
+
+#[test]
+fn test_accept_header() {
+    #[derive(Serialize)]
+    struct ListRequest;
+
+    impl IntoReqwestBuilder for ListRequest {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::GET
+        }
+
+        fn endpoint(&self) -> String {
+            "/items".to_string()
+        }
+
+        fn accept(&self) -> Option<&str> {
+            Some("application/json")
+        }
+
+        fn body(&self) -> RequestBody {
+            RequestBody::None
+        }
+    }
+
+    let base_url = Url::parse("https://api.example.com").unwrap();
+    let http_request = ListRequest.to_http_request(&base_url).unwrap();
+    assert_eq!(
+        http_request.headers().get("Accept").unwrap(),
+        "application/json"
+    );
+}
+
+#[test]
+fn test_body_content_type_defaults() {
+    #[derive(Serialize, Clone)]
+    struct VendorHeaders {
+        #[serde(rename = "Content-Type")]
+        content_type: String,
+    }
+
+    #[derive(Serialize)]
+    struct VendorRequest {
+        name: String,
+    }
+
+    impl IntoReqwestBuilder for VendorRequest {
+        type Headers = VendorHeaders;
+
+        fn method(&self) -> http::Method {
+            http::Method::POST
+        }
+
+        fn endpoint(&self) -> String {
+            "/items".to_string()
+        }
+
+        fn headers(&self) -> Option<Self::Headers> {
+            Some(VendorHeaders {
+                content_type: "application/vnd.api+json".to_string(),
+            })
+        }
+    }
+
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let json = TestRequest {
+        field1: "value1".to_string(),
+        field2: 42,
+        field3: None,
+    }
+    .to_http_request(&base_url)
+    .unwrap();
+    let content_types: Vec<_> = json.headers().get_all("Content-Type").iter().collect();
+    assert_eq!(content_types, vec!["application/json"]);
+
+    // A user-provided Content-Type replaces the default
+    let vendor = VendorRequest {
+        name: "widget".to_string(),
+    }
+    .to_http_request(&base_url)
+    .unwrap();
+    let content_types: Vec<_> = vendor.headers().get_all("Content-Type").iter().collect();
+    assert_eq!(content_types, vec!["application/vnd.api+json"]);
+}
+
+#[test]
+fn test_url_error_from_parse_error() {
+    let parse_err = Url::parse("http://[::1").unwrap_err();
+    let message = parse_err.to_string();
+
+    let err = ReqwestBuilderError::from(parse_err);
+    assert_eq!(err, ReqwestBuilderError::UrlError(message));
+
+    let parse = || -> Result<Url, ReqwestBuilderError> { Ok(Url::parse("no scheme")?) };
+    assert_eq!(
+        parse().unwrap_err(),
+        ReqwestBuilderError::UrlError("relative URL without a base".to_string())
+    );
+}
+
+#[test]
+fn test_request_error_from_reqwest_error() {
+    let reqwest_err = reqwest::Client::new()
+        .get("not a valid url")
+        .build()
+        .unwrap_err();
+    let message = reqwest_err.to_string();
+
+    let err = ReqwestBuilderError::from(reqwest_err);
+    assert_eq!(err, ReqwestBuilderError::RequestError(message.clone()));
+
+    let middleware_err = reqwest_middleware::Error::from(
+        reqwest::Client::new()
+            .get("not a valid url")
+            .build()
+            .unwrap_err(),
+    );
+    let err = ReqwestBuilderError::from(middleware_err);
+    assert_eq!(err, ReqwestBuilderError::RequestError(message));
+}
+
+#[test]
+fn test_error_source_chain() {
+    use std::error::Error;
+
+    let err = FileUpload::from_path("/non/existent/file.txt").unwrap_err();
+    let source = err.source().expect("IoError should keep its source");
+    assert!(source.downcast_ref::<std::io::Error>().is_some());
+    assert!(err.to_string().starts_with("I/O error: "));
+
+    let json_err = serde_json::from_str::<serde_json::Value>("{invalid").unwrap_err();
+    let err = ReqwestBuilderError::from(json_err);
+    let source = err
+        .source()
+        .expect("SerializationError should keep its source");
+    assert!(source.downcast_ref::<serde_json::Error>().is_some());
+}
+
+#[test]
+fn test_extra_headers_merge_with_typed_headers() {
+    #[derive(Serialize, Clone)]
+    struct TypedHeaders {
+        #[serde(rename = "Authorization")]
+        authorization: String,
+        #[serde(rename = "X-Client")]
+        client: String,
+    }
+
+    #[derive(Serialize)]
+    struct HeaderRequest {
+        request_id: String,
+    }
+
+    impl IntoReqwestBuilder for HeaderRequest {
+        type Headers = TypedHeaders;
+
+        fn method(&self) -> http::Method {
+            http::Method::GET
+        }
+
+        fn endpoint(&self) -> String {
+            "/headers".to_string()
+        }
+
+        fn headers(&self) -> Option<Self::Headers> {
+            Some(TypedHeaders {
+                authorization: "Bearer static".to_string(),
+                client: "typed".to_string(),
+            })
+        }
+
+        fn extra_headers(&self) -> Option<http::HeaderMap> {
+            let mut headers = http::HeaderMap::new();
+            headers.insert("X-Request-Id", self.request_id.parse().unwrap());
+            headers.insert("X-Client", "runtime".parse().unwrap());
+            Some(headers)
+        }
+
+        fn body(&self) -> RequestBody {
+            RequestBody::None
+        }
+    }
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = HeaderRequest {
+        request_id: "req-42".to_string(),
+    };
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    let headers = built.headers();
+
+    assert_eq!(headers.get("Authorization").unwrap(), "Bearer static");
+    assert_eq!(headers.get("X-Request-Id").unwrap(), "req-42");
+    assert_eq!(headers.get_all("X-Client").iter().count(), 1);
+    assert_eq!(headers.get("X-Client").unwrap(), "runtime");
+}
+
+#[test]
+fn test_websocket_handshake_headers() {
+    #[derive(Serialize)]
+    struct OpenStream {
+        #[serde(skip)]
+        method: http::Method,
+    }
+
+    impl IntoReqwestBuilder for OpenStream {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            self.method.clone()
+        }
+
+        fn endpoint(&self) -> String {
+            "/stream".to_string()
+        }
+
+        fn websocket(&self) -> bool {
+            true
+        }
+
+        fn body(&self) -> RequestBody {
+            RequestBody::None
+        }
+    }
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let built = OpenStream {
+        method: http::Method::GET,
+    }
+    .into_reqwest_builder(&client, &base_url)
+    .unwrap()
+    .build()
+    .unwrap();
+    let headers = built.headers();
+
+    assert_eq!(headers.get("Connection").unwrap(), "Upgrade");
+    assert_eq!(headers.get("Upgrade").unwrap(), "websocket");
+    assert_eq!(headers.get("Sec-WebSocket-Version").unwrap(), "13");
+    let key = headers.get("Sec-WebSocket-Key").unwrap().to_str().unwrap();
+    assert_eq!(key.len(), 24);
+    assert_ne!(key, reqwest_builder::websocket_key());
+
+    let post = OpenStream {
+        method: http::Method::POST,
+    }
+    .into_reqwest_builder(&client, &base_url)
+    .expect_err("WebSocket handshakes require GET");
+    assert!(matches!(post, ReqwestBuilderError::InvalidRequest(_)));
+}
+
+#[test]
+fn test_conditional_request_headers() {
+    #[derive(Serialize)]
+    struct GetFeed {
+        etag: Option<String>,
+        last_modified: Option<String>,
+    }
+
+    impl IntoReqwestBuilder for GetFeed {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::GET
+        }
+
+        fn endpoint(&self) -> String {
+            "/feed".to_string()
+        }
+
+        fn if_none_match(&self) -> Option<String> {
+            self.etag.clone()
+        }
+
+        fn if_modified_since(&self) -> Option<String> {
+            self.last_modified.clone()
+        }
+
+        fn body(&self) -> RequestBody {
+            RequestBody::None
+        }
+    }
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+    let build = |request: GetFeed| {
+        request
+            .into_reqwest_builder(&client, &base_url)
+            .unwrap()
+            .build()
+            .unwrap()
+    };
+
+    let cached = build(GetFeed {
+        etag: Some("\"v42\"".to_string()),
+        last_modified: Some("Wed, 21 Oct 2015 07:28:00 GMT".to_string()),
+    });
+    assert_eq!(cached.headers().get("If-None-Match").unwrap(), "\"v42\"");
+    assert_eq!(
+        cached.headers().get("If-Modified-Since").unwrap(),
+        "Wed, 21 Oct 2015 07:28:00 GMT"
+    );
+
+    let fresh = build(GetFeed {
+        etag: None,
+        last_modified: None,
+    });
+    assert!(fresh.headers().get("If-None-Match").is_none());
+    assert!(fresh.headers().get("If-Modified-Since").is_none());
+}
+
+#[test]
+fn test_idempotency_key_header() {
+    #[derive(Serialize)]
+    struct CreateCharge {
+        amount: u64,
+        #[serde(skip)]
+        idempotency_key: Option<String>,
+    }
+
+    impl IntoReqwestBuilder for CreateCharge {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::POST
+        }
+
+        fn endpoint(&self) -> String {
+            "/charges".to_string()
+        }
+
+        fn idempotency_key(&self) -> Option<String> {
+            self.idempotency_key.clone()
+        }
+    }
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let built = CreateCharge {
+        amount: 100,
+        idempotency_key: Some("order-42".to_string()),
+    }
+    .into_reqwest_builder(&client, &base_url)
+    .unwrap()
+    .build()
+    .unwrap();
+    assert_eq!(built.headers().get("Idempotency-Key").unwrap(), "order-42");
+
+    let without_key = CreateCharge {
+        amount: 100,
+        idempotency_key: None,
+    }
+    .into_reqwest_builder(&client, &base_url)
+    .unwrap()
+    .build()
+    .unwrap();
+    assert!(without_key.headers().get("Idempotency-Key").is_none());
+}
+
+#[test]
+fn test_customize_runs_last() {
+    #[derive(Serialize)]
+    struct Versioned;
+
+    impl IntoReqwestBuilder for Versioned {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::GET
+        }
+
+        fn endpoint(&self) -> String {
+            "/status".to_string()
+        }
+
+        fn extra_headers(&self) -> Option<http::HeaderMap> {
+            let mut headers = http::HeaderMap::new();
+            headers.insert("X-Source", "extra".parse().unwrap());
+            Some(headers)
+        }
+
+        fn body(&self) -> RequestBody {
+            RequestBody::None
+        }
+
+        fn customize(
+            &self,
+            builder: reqwest_middleware::RequestBuilder,
+        ) -> reqwest_middleware::RequestBuilder {
+            builder
+                .header("X-Source", "customize")
+                .version(http::Version::HTTP_11)
+        }
+    }
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let built = Versioned
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let sources: Vec<_> = built.headers().get_all("X-Source").iter().collect();
+    assert_eq!(sources, vec!["extra", "customize"]);
+    assert_eq!(built.version(), http::Version::HTTP_11);
+}
+
+#[test]
+fn test_full_url_bypasses_base_url() {
+    #[derive(Serialize)]
+    struct DownloadRequest {
+        signed_url: String,
+    }
+
+    impl IntoReqwestBuilder for DownloadRequest {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::GET
+        }
+
+        fn endpoint(&self) -> String {
+            "/unused".to_string()
+        }
+
+        fn full_url(&self) -> Option<String> {
+            Some(self.signed_url.clone())
+        }
+
+        fn body(&self) -> RequestBody {
+            RequestBody::None
+        }
+    }
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com/v2").unwrap();
+
+    let signed = "https://cdn.example.net/files/report.pdf?X-Signature=abc%2Fdef&Expires=60";
+    let built = DownloadRequest {
+        signed_url: signed.to_string(),
+    }
+    .into_reqwest_builder(&client, &base_url)
+    .unwrap()
+    .build()
+    .unwrap();
+    assert_eq!(built.url().as_str(), signed);
+
+    let invalid = DownloadRequest {
+        signed_url: "not a url".to_string(),
+    }
+    .into_reqwest_builder(&client, &base_url)
+    .expect_err("an unparsable full URL should be rejected");
+    assert!(matches!(invalid, ReqwestBuilderError::UrlError(_)));
+}
+
+#[test]
+fn test_allow_empty_json_body() {
+    #[derive(Serialize)]
+    struct TriggerJob {}
+
+    impl IntoReqwestBuilder for TriggerJob {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::POST
+        }
+
+        fn endpoint(&self) -> String {
+            "/jobs/trigger".to_string()
+        }
+
+        fn allow_empty_json_body(&self) -> bool {
+            true
+        }
+    }
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let built = TriggerJob {}
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        built.headers().get("Content-Type").unwrap(),
+        "application/json"
+    );
+    assert_eq!(built.body().unwrap().as_bytes().unwrap(), b"{}");
+}
+
+#[test]
+fn test_pretty_json_body() {
+    #[derive(Serialize)]
+    struct CreateNote {
+        title: String,
+        tags: Vec<String>,
+    }
+
+    impl IntoReqwestBuilder for CreateNote {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::POST
+        }
+
+        fn endpoint(&self) -> String {
+            "/notes".to_string()
+        }
+
+        fn pretty_json(&self) -> bool {
+            true
+        }
+    }
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let built = CreateNote {
+        title: "Hello".to_string(),
+        tags: vec!["a".to_string()],
+    }
+    .into_reqwest_builder(&client, &base_url)
+    .unwrap()
+    .build()
+    .unwrap();
+
+    assert_eq!(
+        built.headers().get("Content-Type").unwrap(),
+        "application/json"
+    );
+    let body = std::str::from_utf8(built.body().unwrap().as_bytes().unwrap()).unwrap();
+    assert_eq!(
+        body,
+        "{\n  \"tags\": [\n    \"a\"\n  ],\n  \"title\": \"Hello\"\n}"
+    );
+}
+
+#[test]
+fn test_header_map_applied_after_typed_headers() {
+    #[derive(Serialize, Clone)]
+    struct TypedHeaders {
+        #[serde(rename = "X-Client")]
+        client: String,
+        #[serde(rename = "X-Version")]
+        version: String,
+    }
+
+    #[derive(Serialize)]
+    struct MapRequest;
+
+    impl IntoReqwestBuilder for MapRequest {
+        type Headers = TypedHeaders;
+
+        fn method(&self) -> http::Method {
+            http::Method::GET
+        }
+
+        fn endpoint(&self) -> String {
+            "/headers".to_string()
+        }
+
+        fn headers(&self) -> Option<Self::Headers> {
+            Some(TypedHeaders {
+                client: "typed".to_string(),
+                version: "1".to_string(),
+            })
+        }
+
+        fn header_map(&self) -> Option<http::HeaderMap> {
+            let mut headers = http::HeaderMap::new();
+            headers.insert("X-Client", "prebuilt".parse().unwrap());
+            headers.append("X-Feature", "a".parse().unwrap());
+            headers.append("X-Feature", "b".parse().unwrap());
+            Some(headers)
+        }
+
+        fn body(&self) -> RequestBody {
+            RequestBody::None
+        }
+    }
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let built = MapRequest
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    let headers = built.headers();
+
+    assert_eq!(headers.get_all("X-Client").iter().count(), 1);
+    assert_eq!(headers.get("X-Client").unwrap(), "prebuilt");
+    assert_eq!(headers.get("X-Version").unwrap(), "1");
+    let features: Vec<_> = headers.get_all("X-Feature").iter().collect();
+    assert_eq!(features, vec!["a", "b"]);
+}
+
+#[test]
+fn test_extra_query_params_merge_with_derived_params() {
+    #[derive(Serialize)]
+    struct SearchRequest {
+        trace: String,
+    }
+
+    impl IntoReqwestBuilder for SearchRequest {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::GET
+        }
+
+        fn endpoint(&self) -> String {
+            "/search".to_string()
+        }
+
+        fn query_params(&self) -> Option<QueryParams> {
+            Some(vec![
+                ("tag".to_string(), "a".to_string()),
+                ("page".to_string(), "1".to_string()),
+                ("tag".to_string(), "b".to_string()),
+            ])
+        }
+
+        fn extra_query_params(&self) -> Option<QueryParams> {
+            Some(vec![
+                ("trace".to_string(), self.trace.clone()),
+                ("page".to_string(), "2".to_string()),
+            ])
+        }
+
+        fn body(&self) -> RequestBody {
+            RequestBody::None
+        }
+    }
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = SearchRequest {
+        trace: "abc".to_string(),
+    };
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        built.url().as_str(),
+        "https://api.example.com/search?tag=a&tag=b&trace=abc&page=2"
+    );
+}
+
+#[test]
+fn test_empty_query_key_is_rejected() {
+    #[derive(Serialize)]
+    struct BadQuery;
+
+    impl IntoReqwestBuilder for BadQuery {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::GET
+        }
+
+        fn endpoint(&self) -> String {
+            "/search".to_string()
+        }
+
+        fn query_params(&self) -> Option<QueryParams> {
+            Some(vec![
+                ("q".to_string(), "rust".to_string()),
+                (String::new(), "orphan".to_string()),
+            ])
+        }
+
+        fn body(&self) -> RequestBody {
+            RequestBody::None
+        }
+    }
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let err = BadQuery
+        .into_reqwest_builder(&client, &base_url)
+        .expect_err("an empty query key should be rejected");
+    assert_eq!(
+        err,
+        ReqwestBuilderError::QueryError {
+            key: String::new(),
+            reason: "Query parameter key must not be empty".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_query_string_is_sorted_and_encoded() {
+    #[derive(Serialize)]
+    struct SignedSearch;
+
+    impl IntoReqwestBuilder for SignedSearch {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::GET
+        }
+
+        fn endpoint(&self) -> String {
+            "/search".to_string()
+        }
+
+        fn query_params(&self) -> Option<QueryParams> {
+            Some(vec![
+                ("q".to_string(), "rust & tokio".to_string()),
+                ("tag".to_string(), "web".to_string()),
+                ("limit".to_string(), "10".to_string()),
+                ("tag".to_string(), "async".to_string()),
+            ])
+        }
+
+        fn body(&self) -> RequestBody {
+            RequestBody::None
+        }
+    }
+
+    assert_eq!(
+        SignedSearch.query_string().as_deref(),
+        Some("limit=10&q=rust+%26+tokio&tag=async&tag=web")
+    );
+
+    let no_params = TestRequest {
+        field1: "value".to_string(),
+        field2: 1,
+        field3: None,
+    };
+    assert_eq!(no_params.query_string(), None);
+}
+
+#[test]
+fn test_debug_summary_redacts_sensitive_headers() {
+    #[derive(Serialize, Clone)]
+    struct SummaryHeaders {
+        #[serde(rename = "Authorization")]
+        authorization: String,
+        #[serde(rename = "X-Api-Key")]
+        api_key: String,
+        #[serde(rename = "X-Trace")]
+        trace: String,
+    }
+
+    #[derive(Serialize)]
+    struct CreateUser {
+        name: String,
+    }
+
+    impl IntoReqwestBuilder for CreateUser {
+        type Headers = SummaryHeaders;
+
+        fn method(&self) -> http::Method {
+            http::Method::POST
+        }
+
+        fn endpoint(&self) -> String {
+            "/users".to_string()
+        }
+
+        fn headers(&self) -> Option<Self::Headers> {
+            Some(SummaryHeaders {
+                authorization: "Bearer secret-token".to_string(),
+                api_key: "key-123".to_string(),
+                trace: "abc".to_string(),
+            })
+        }
+
+        fn query_params(&self) -> Option<QueryParams> {
+            Some(vec![("draft".to_string(), "true".to_string())])
+        }
+
+        fn sensitive_headers(&self) -> &[&str] {
+            &["authorization", "X-API-KEY"]
+        }
+    }
+
+    let request = CreateUser {
+        name: "Ada".to_string(),
+    };
+    let summary = request.debug_summary();
+
+    assert_eq!(
+        summary,
+        "POST /users?draft=true [headers: authorization(redacted), x-api-key(redacted), x-trace=abc]"
+    );
+    assert!(!summary.contains("secret-token"));
+    assert!(!summary.contains("Ada"));
+}
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn test_streaming_file_upload() {
+    use reqwest_builder::FileContent;
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let path = std::env::temp_dir().join("reqwest_builder_streaming_upload.txt");
+    std::fs::write(&path, b"streamed file contents").unwrap();
+
+    let upload = FileUpload::from_path_streaming(&path).unwrap();
+    assert_eq!(upload.filename, "reqwest_builder_streaming_upload.txt");
+    assert_eq!(upload.len(), 22);
+    assert_eq!(upload.mime_type, Some("text/plain".to_string()));
+    assert!(matches!(upload.content, FileContent::Path { .. }));
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let form = reqwest::multipart::Form::new().part("file", upload.to_multipart_part());
+    reqwest::Client::new()
+        .post(server.uri())
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+
+    let received = server.received_requests().await.unwrap();
+    let body = String::from_utf8_lossy(&received[0].body);
+    assert!(body.contains("filename=\"reqwest_builder_streaming_upload.txt\""));
+    assert!(body.contains("streamed file contents"));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_file_upload_from_path_async() {
+    let path = std::env::temp_dir().join("reqwest_builder_async_upload.json");
+    std::fs::write(&path, b"{\"async\": true}").unwrap();
+
+    let async_upload = FileUpload::from_path_async(&path).await.unwrap();
+    let blocking_upload = FileUpload::from_path(&path).unwrap();
+    assert_eq!(async_upload, blocking_upload);
+    assert_eq!(async_upload.mime_type, Some("application/json".to_string()));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_file_upload_from_path_with_limit() {
+    let path = std::env::temp_dir().join("reqwest_builder_limited_upload.bin");
+    std::fs::write(&path, [0u8; 16]).unwrap();
+
+    let upload = FileUpload::from_path_with_limit(&path, 16).unwrap();
+    assert_eq!(upload.len(), 16);
+
+    match FileUpload::from_path_with_limit(&path, 8).unwrap_err() {
+        ReqwestBuilderError::InvalidRequest(msg) => {
+            assert!(msg.contains("is 16 bytes, exceeding the limit of 8 bytes"));
+        }
+        other => panic!("Expected InvalidRequest, got {:?}", other),
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_file_upload_from_reader() {
+    use reqwest_builder::FileContent;
+
+    let reader = std::io::Cursor::new(b"id,name\n1,alice\n".to_vec());
+    let upload = FileUpload::from_reader(
+        "users.csv".to_string(),
+        reader,
+        Some("text/csv".to_string()),
+    )
+    .unwrap();
+
+    assert_eq!(upload.filename, "users.csv");
+    assert_eq!(
+        upload.content,
+        FileContent::Bytes(b"id,name\n1,alice\n".to_vec())
+    );
+    assert_eq!(upload.mime_type, Some("text/csv".to_string()));
+}
+
+#[test]
+fn test_file_content_path_and_as_bytes() {
+    use reqwest_builder::FileContent;
+
+    let path = std::env::temp_dir().join("reqwest_builder_path_content.txt");
+    std::fs::write(&path, b"on disk").unwrap();
+
+    let upload = FileUpload {
+        filename: "notes.txt".to_string(),
+        content: FileContent::Path {
+            path: path.clone(),
+            len: 7,
+        },
+        mime_type: Some("text/plain".to_string()),
+    };
+    assert_eq!(upload.len(), 7);
+    assert_eq!(upload.content.as_bytes(), None);
+    assert!(upload.try_to_multipart_part().is_ok());
+
+    let in_memory = FileUpload::from_bytes("notes.txt".to_string(), b"hi".to_vec(), None);
+    assert_eq!(in_memory.content.as_bytes(), Some(b"hi".as_slice()));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_file_upload_with_mime_type() {
+    let path = std::env::temp_dir().join("reqwest_builder_mime_override");
+    std::fs::write(&path, b"a,b\n1,2\n").unwrap();
+
+    let upload = FileUpload::from_path(&path)
+        .unwrap()
+        .with_mime_type("text/csv")
+        .unwrap();
+    assert_eq!(upload.mime_type, Some("text/csv".to_string()));
+
+    let result = FileUpload::from_path(&path)
+        .unwrap()
+        .with_mime_type("not a mime");
+    match result.unwrap_err() {
+        ReqwestBuilderError::InvalidRequest(msg) => {
+            assert!(msg.contains("Invalid MIME type 'not a mime'"));
+        }
+        other => panic!("Expected InvalidRequest, got {:?}", other),
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_ndjson_body() {
+    #[derive(Serialize)]
+    struct IngestRequest {
+        events: Vec<serde_json::Value>,
+    }
+
+    impl IntoReqwestBuilder for IngestRequest {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::POST
+        }
+
+        fn endpoint(&self) -> String {
+            "/ingest".to_string()
+        }
+
+        fn body(&self) -> RequestBody {
+            RequestBody::Ndjson
+        }
+
+        fn ndjson_records(&self) -> Option<Vec<serde_json::Value>> {
+            Some(self.events.clone())
+        }
+    }
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = IngestRequest {
+        events: vec![
+            serde_json::json!({ "id": 1 }),
+            serde_json::json!({ "id": 2, "tag": "b" }),
+        ],
+    };
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        built.headers().get("Content-Type").unwrap(),
+        "application/x-ndjson"
+    );
+    assert_eq!(
+        built.body().unwrap().as_bytes(),
+        Some("{\"id\":1}\n{\"id\":2,\"tag\":\"b\"}\n".as_bytes())
+    );
+}
+
+#[test]
+fn test_graphql_body() {
+    use reqwest_builder::GraphQLBody;
+
+    #[derive(Serialize)]
+    struct UserQuery {
+        id: u64,
+    }
+
+    impl IntoReqwestBuilder for UserQuery {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::POST
+        }
+
+        fn endpoint(&self) -> String {
+            "/graphql".to_string()
+        }
+
+        fn body(&self) -> RequestBody {
+            RequestBody::GraphQL
+        }
+
+        fn graphql_body(&self) -> Option<GraphQLBody> {
+            Some(
+                GraphQLBody::new("query User($id: ID!) { user(id: $id) { name } }")
+                    .with_variables(serde_json::json!({ "id": self.id }))
+                    .with_operation_name("User"),
+            )
+        }
+    }
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let built = UserQuery { id: 7 }
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(built.method(), http::Method::POST);
+    assert_eq!(
+        built.headers().get("Content-Type").unwrap(),
+        "application/json"
+    );
+    let envelope: serde_json::Value =
+        serde_json::from_slice(built.body().unwrap().as_bytes().unwrap()).unwrap();
+    assert_eq!(
+        envelope,
+        serde_json::json!({
+            "query": "query User($id: ID!) { user(id: $id) { name } }",
+            "variables": { "id": 7 },
+            "operationName": "User",
+        })
+    );
+
+    let minimal = serde_json::to_value(GraphQLBody::new("{ viewer { id } }")).unwrap();
+    assert_eq!(minimal, serde_json::json!({ "query": "{ viewer { id } }" }));
+}
+
+#[test]
+fn test_json_patch_body() {
+    use reqwest_builder::JsonPatch;
+
+    #[derive(Serialize)]
+    struct PatchUser {
+        id: u64,
+    }
+
+    impl IntoReqwestBuilder for PatchUser {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::PATCH
+        }
+
+        fn endpoint(&self) -> String {
+            format!("/users/{}", self.id)
+        }
+
+        fn body(&self) -> RequestBody {
+            RequestBody::JsonPatch
+        }
+
+        fn json_patch(&self) -> Option<JsonPatch> {
+            Some(JsonPatch::new().replace("/name", "Ada").remove("/nickname"))
+        }
+    }
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let built = PatchUser { id: 7 }
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        built.headers().get("Content-Type").unwrap(),
+        "application/json-patch+json"
+    );
+    let operations: serde_json::Value =
+        serde_json::from_slice(built.body().unwrap().as_bytes().unwrap()).unwrap();
+    assert_eq!(
+        operations,
+        serde_json::json!([
+            { "op": "replace", "path": "/name", "value": "Ada" },
+            { "op": "remove", "path": "/nickname" },
+        ])
+    );
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn test_gzip_compressed_json_body() {
+    use std::io::Read;
+
+    #[derive(Serialize)]
+    struct LargeRequest {
+        items: Vec<String>,
+    }
+
+    impl IntoReqwestBuilder for LargeRequest {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::POST
+        }
+
+        fn endpoint(&self) -> String {
+            "/bulk".to_string()
+        }
+
+        fn compress_body(&self) -> bool {
+            true
+        }
+    }
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = LargeRequest {
+        items: vec!["item".to_string(); 100],
+    };
+    let original = serde_json::to_value(&request).unwrap();
+
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(built.headers().get("Content-Encoding").unwrap(), "gzip");
+    assert_eq!(
+        built.headers().get("Content-Type").unwrap(),
+        "application/json"
+    );
+
+    let mut decoder = flate2::read::GzDecoder::new(built.body().unwrap().as_bytes().unwrap());
+    let mut decompressed = String::new();
+    decoder.read_to_string(&mut decompressed).unwrap();
+    let decoded: serde_json::Value = serde_json::from_str(&decompressed).unwrap();
+    assert_eq!(decoded, original);
+}
+
+#[test]
+fn test_xml_body() {
+    #[derive(Serialize)]
+    struct SoapRequest {
+        account: String,
+    }
+
+    impl IntoReqwestBuilder for SoapRequest {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::POST
+        }
+
+        fn endpoint(&self) -> String {
+            "/soap".to_string()
+        }
+
+        fn body(&self) -> RequestBody {
+            RequestBody::Xml
+        }
+
+        fn xml_body(&self) -> Option<String> {
+            Some(format!(
+                "<GetBalance><Account>{}</Account></GetBalance>",
+                self.account
+            ))
+        }
+    }
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = SoapRequest {
+        account: "42".to_string(),
+    };
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        built.headers().get("Content-Type").unwrap(),
+        "application/xml"
+    );
+    assert_eq!(
+        built.body().unwrap().as_bytes().unwrap(),
+        b"<GetBalance><Account>42</Account></GetBalance>"
+    );
+}
+
+#[test]
+fn test_custom_json_body_serializer() {
+    #[derive(Serialize)]
+    struct PrettyRequest {
+        name: String,
+    }
+
+    impl IntoReqwestBuilder for PrettyRequest {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::POST
+        }
+
+        fn endpoint(&self) -> String {
+            "/pretty".to_string()
+        }
+
+        fn serialize_json_body(&self) -> Result<Vec<u8>, ReqwestBuilderError> {
+            Ok(serde_json::to_vec_pretty(self)?)
+        }
+    }
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = PrettyRequest {
+        name: "test".to_string(),
+    };
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        built.headers().get("Content-Type").unwrap(),
+        "application/json"
+    );
+    assert_eq!(
+        built.body().unwrap().as_bytes().unwrap(),
+        b"{\n  \"name\": \"test\"\n}"
+    );
+}
+
+#[test]
+fn test_non_object_body_is_rejected() {
+    #[derive(Serialize)]
+    struct Inner {
+        id: u32,
+    }
+
+    #[derive(Serialize)]
+    struct Batch(Vec<Inner>);
+
+    impl IntoReqwestBuilder for Batch {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::POST
+        }
+
+        fn endpoint(&self) -> String {
+            "/batch".to_string()
+        }
+
+        fn body(&self) -> RequestBody {
+            RequestBody::Form
+        }
+    }
+
+    #[derive(Serialize)]
+    struct Count(u32);
+
+    impl IntoReqwestBuilder for Count {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::POST
+        }
+
+        fn endpoint(&self) -> String {
+            "/count".to_string()
+        }
+    }
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let form = Batch(vec![Inner { id: 1 }]).into_reqwest_builder(&client, &base_url);
+    assert_eq!(
+        form.err(),
+        Some(ReqwestBuilderError::InvalidRequest(
+            "Form body must serialize to an object, got [{\"id\":1}]".to_string()
+        ))
+    );
+
+    let json = Count(3).into_reqwest_builder(&client, &base_url);
+    assert!(matches!(
+        json,
+        Err(ReqwestBuilderError::InvalidRequest(message)) if message.contains("got 3")
+    ));
+}
+
+#[test]
+fn test_json_array_body_is_accepted() {
+    #[derive(Serialize)]
+    struct Ids(Vec<u32>);
+
+    impl IntoReqwestBuilder for Ids {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::POST
+        }
+
+        fn endpoint(&self) -> String {
+            "/ids".to_string()
+        }
+    }
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let built = Ids(vec![1, 2])
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(built.body().unwrap().as_bytes().unwrap(), b"[1,2]");
+}
+
+#[tokio::test]
+async fn test_header_name_casing_on_the_wire() {
+    use std::io::{Read, Write};
+
+    #[derive(Serialize, Clone)]
+    struct LowerHeaders {
+        #[serde(rename = "x-custom-id")]
+        id: String,
+    }
+
+    #[derive(Serialize, Clone)]
+    struct TitleHeaders {
+        #[serde(rename = "X-Custom-Id")]
+        id: String,
+    }
+
+    let lower = serialize_to_header_map(&LowerHeaders {
+        id: "1".to_string(),
+    })
+    .unwrap();
+    let title = serialize_to_header_map(&TitleHeaders {
+        id: "1".to_string(),
+    })
+    .unwrap();
+    assert_eq!(lower, title);
+    assert_eq!(lower.keys().next().unwrap().as_str(), "x-custom-id");
+
+    #[derive(Serialize)]
+    struct PingRequest;
+
+    impl IntoReqwestBuilder for PingRequest {
+        type Headers = TitleHeaders;
+
+        fn method(&self) -> http::Method {
+            http::Method::GET
+        }
+
+        fn endpoint(&self) -> String {
+            "/ping".to_string()
+        }
+
+        fn headers(&self) -> Option<Self::Headers> {
+            Some(TitleHeaders {
+                id: "1".to_string(),
+            })
+        }
+
+        fn body(&self) -> RequestBody {
+            RequestBody::None
+        }
+    }
+
+    // Capture the raw request head, since parsed headers lose their casing
+    async fn raw_request_head(client: reqwest::Client) -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut head = Vec::new();
+            let mut buf = [0; 1024];
+            while !head.ends_with(b"\r\n\r\n") {
+                let read = stream.read(&mut buf).unwrap();
+                if read == 0 {
+                    break;
+                }
+                head.extend_from_slice(&buf[..read]);
+            }
+            stream
+                .write_all(b"HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n")
+                .unwrap();
+            String::from_utf8(head).unwrap()
+        });
+
+        let client = reqwest_middleware::ClientBuilder::new(client).build();
+        PingRequest.send(&client, &base_url).await.unwrap();
+        server.join().unwrap()
+    }
+
+    let head = raw_request_head(reqwest::Client::new()).await;
+    assert!(head.contains("\r\nx-custom-id: 1\r\n"));
+
+    let title_case_client = reqwest::Client::builder()
+        .http1_title_case_headers()
+        .build()
+        .unwrap();
+    let head = raw_request_head(title_case_client).await;
+    assert!(head.contains("\r\nX-Custom-Id: 1\r\n"));
+}
+
+#[test]
+fn test_endpoint_with_existing_query_string() {
+    #[derive(Serialize)]
+    struct SearchRequest {
+        page: u32,
+    }
+
+    impl IntoReqwestBuilder for SearchRequest {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::GET
+        }
+
+        fn endpoint(&self) -> String {
+            "/search?q=foo&sort=".to_string()
+        }
+
+        fn body(&self) -> RequestBody {
+            RequestBody::None
+        }
+
+        fn query_params(&self) -> Option<QueryParams> {
+            Some(vec![
+                ("page".to_string(), self.page.to_string()),
+                ("q".to_string(), "bar baz".to_string()),
+            ])
+        }
+    }
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+
+    let base_url = Url::parse("https://api.example.com/v1").unwrap();
+    let built = SearchRequest { page: 2 }
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(
+        built.url().as_str(),
+        "https://api.example.com/v1/search?q=foo&sort=&page=2&q=bar+baz"
+    );
+
+    let base_url = Url::parse("https://api.example.com/v1?key=abc").unwrap();
+    let built = SearchRequest { page: 3 }
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(
+        built.url().as_str(),
+        "https://api.example.com/v1/search?key=abc&q=foo&sort=&page=3&q=bar+baz"
+    );
+}
+
+#[test]
+fn test_merge_query_params() {
+    use reqwest_builder::merge_query_params;
+
+    let params = vec![("page".to_string(), "2".to_string())];
+
+    let mut url = Url::parse("https://api.example.com/search?").unwrap();
+    merge_query_params(&mut url, &params);
+    assert_eq!(url.as_str(), "https://api.example.com/search?page=2");
+
+    let mut url = Url::parse("https://api.example.com/search?q=a%26b").unwrap();
+    merge_query_params(&mut url, &params);
+    assert_eq!(
+        url.as_str(),
+        "https://api.example.com/search?q=a%26b&page=2"
+    );
+
+    let mut url = Url::parse("https://api.example.com/search").unwrap();
+    merge_query_params(&mut url, &Vec::new());
+    assert_eq!(url.as_str(), "https://api.example.com/search");
+}
+
+#[test]
+fn test_build_request_is_repeatable() {
+    let request = TestRequest {
+        field1: "value1".to_string(),
+        field2: 42,
+        field3: None,
+    };
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let first = request
+        .build_request(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    let second = request
+        .build_request(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(first.url(), second.url());
+    assert_eq!(first.method(), second.method());
+    assert_eq!(
+        first.body().unwrap().as_bytes(),
+        second.body().unwrap().as_bytes()
+    );
+
+    // The request is still usable afterwards
+    assert!(request.into_reqwest_builder(&client, &base_url).is_ok());
+}
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn test_stream_body_is_received_intact() {
+    use std::sync::Mutex;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[derive(Serialize)]
+    struct ProxyUpload {
+        #[serde(skip)]
+        source: Mutex<Option<std::io::Cursor<Vec<u8>>>>,
+    }
+
+    impl IntoReqwestBuilder for ProxyUpload {
+        type Headers = ();
+
+        fn method(&self) -> http::Method {
+            http::Method::PUT
+        }
+
+        fn endpoint(&self) -> String {
+            "/uploads".to_string()
+        }
+
+        fn body(&self) -> RequestBody {
+            RequestBody::Stream
+        }
+
+        fn body_stream(&self) -> Option<reqwest::Body> {
+            self.source
+                .lock()
+                .unwrap()
+                .take()
+                .map(reqwest_builder::stream_body)
+        }
+    }
+
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(path("/uploads"))
+        .respond_with(ResponseTemplate::new(204))
+        .mount(&server)
+        .await;
+
+    let payload: Vec<u8> = (0..20_000u32).map(|i| (i % 251) as u8).collect();
+    let request = ProxyUpload {
+        source: Mutex::new(Some(std::io::Cursor::new(payload.clone()))),
+    };
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse(&server.uri()).unwrap();
+    let response = request.send(&client, &base_url).await.unwrap();
+    assert_eq!(response.status(), 204);
+
+    let received = server.received_requests().await.unwrap();
+    assert_eq!(received[0].body, payload);
+    assert_eq!(
+        received[0].headers.get("transfer-encoding").unwrap(),
+        "chunked"
+    );
+}
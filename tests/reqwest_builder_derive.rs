@@ -1,5 +1,6 @@
-use reqwest_builder::{IntoReqwestBuilder, RequestBody};
+use reqwest_builder::{FileUpload, IntoReqwestBuilder, RequestBody, ReqwestBuilderError};
 use serde::Serialize;
+use std::collections::HashMap;
 use url::Url;
 
 // Test struct with all attribute types
@@ -43,6 +44,26 @@ struct FormTestRequest {
     password: String,
 }
 
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "POST", path = "/login", body = "form")]
+struct FormWithHeaderRequest {
+    #[header(name = "X-Client-Id")]
+    client_id: String,
+    username: String,
+    password: String,
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "POST", path = "/search")]
+struct SkippedBodyFieldsRequest {
+    #[query]
+    dry_run: bool,
+    #[serde(skip)]
+    #[allow(dead_code)]
+    trace: String,
+    term: String,
+}
+
 #[derive(Serialize, IntoReqwestBuilder)]
 #[request(method = "DELETE", path = "/resource/{id}", body = "none")]
 struct DeleteTestRequest {
@@ -73,7 +94,7 @@ fn test_complete_derive_macro() {
     assert_eq!(request.endpoint(), "/api/users/123/posts");
 
     // Test query parameters
-    let query_params = request.query_params().unwrap();
+    let query_params: HashMap<_, _> = request.query_params().unwrap().into_iter().collect();
     assert_eq!(query_params.get("draft"), Some(&"true".to_string()));
     assert_eq!(
         query_params.get("include_comments"),
@@ -89,6 +110,112 @@ fn test_complete_derive_macro() {
     assert_eq!(request.body(), RequestBody::Json);
 }
 
+#[test]
+fn test_json_body_excludes_non_body_fields() {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = CompleteTestRequest {
+        id: 123,
+        draft: Some(true),
+        include_comments: Some(false),
+        auth_token: "Bearer test123".to_string(),
+        content_type: "application/json".to_string(),
+        title: "Test Post".to_string(),
+        content: "This is a test post content".to_string(),
+        tags: vec!["test".to_string()],
+    };
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let body: serde_json::Value =
+        serde_json::from_slice(built.body().unwrap().as_bytes().unwrap()).unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "title": "Test Post",
+            "content": "This is a test post content",
+            "tags": ["test"],
+        })
+    );
+}
+
+#[test]
+fn test_form_body_excludes_header_fields() {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = FormWithHeaderRequest {
+        client_id: "client-42".to_string(),
+        username: "alice".to_string(),
+        password: "hunter2".to_string(),
+    };
+    let params =
+        reqwest_builder::serialize_to_form_params(&request.serialize_body().unwrap()).unwrap();
+    assert_eq!(params.len(), 2);
+    assert!(!params.contains_key("client_id"));
+
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    let body = std::str::from_utf8(built.body().unwrap().as_bytes().unwrap()).unwrap();
+    assert!(!body.contains("client"));
+    assert_eq!(built.headers().get("X-Client-Id").unwrap(), "client-42");
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(
+    method = "POST",
+    path = "/filters",
+    body = "form",
+    form_arrays = "brackets"
+)]
+struct SaveFilterRequest {
+    name: String,
+    tags: Vec<String>,
+}
+
+#[test]
+fn test_form_body_array_brackets() {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = SaveFilterRequest {
+        name: "my filter".to_string(),
+        tags: vec!["rust".to_string(), "web dev".to_string()],
+    };
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    let body = std::str::from_utf8(built.body().unwrap().as_bytes().unwrap()).unwrap();
+    assert_eq!(body, "name=my+filter&tags%5B%5D=rust&tags%5B%5D=web+dev");
+}
+
+#[test]
+fn test_query_and_serde_skip_fields_not_in_json_body() {
+    let request = SkippedBodyFieldsRequest {
+        dry_run: true,
+        trace: "abc".to_string(),
+        term: "rust".to_string(),
+    };
+
+    assert_eq!(
+        request.query_params().unwrap(),
+        vec![("dry_run".to_string(), "true".to_string())]
+    );
+    assert_eq!(
+        request.serialize_body().unwrap(),
+        serde_json::json!({ "term": "rust" })
+    );
+}
+
 #[test]
 fn test_simple_get_request() {
     let request = SimpleTestRequest { page: Some(2) };
@@ -96,13 +223,54 @@ fn test_simple_get_request() {
     assert_eq!(request.method(), http::Method::GET);
     assert_eq!(request.endpoint(), "/simple");
 
-    let query_params = request.query_params().unwrap();
+    let query_params: HashMap<_, _> = request.query_params().unwrap().into_iter().collect();
     assert_eq!(query_params.get("page"), Some(&"2".to_string()));
 
     // Should not have headers
     assert!(request.headers().is_none());
 
-    assert_eq!(request.body(), RequestBody::Json);
+    // GET requests default to no body
+    assert_eq!(request.body(), RequestBody::None);
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/reports")]
+struct DefaultBodyGetRequest {
+    #[query]
+    page: u32,
+
+    // Unmarked, but a GET has no body unless one is asked for
+    filter: String,
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/search", body = "json")]
+struct ExplicitBodyGetRequest {
+    filter: String,
+}
+
+#[test]
+fn test_get_defaults_to_no_body() {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = DefaultBodyGetRequest {
+        page: 1,
+        filter: "open".to_string(),
+    };
+    assert_eq!(request.body(), RequestBody::None);
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert!(built.body().is_none());
+    assert!(built.headers().get("Content-Type").is_none());
+
+    let explicit = ExplicitBodyGetRequest {
+        filter: "open".to_string(),
+    };
+    assert_eq!(explicit.body(), RequestBody::Json);
 }
 
 #[test]
@@ -138,7 +306,7 @@ fn test_optional_query_params() {
     let request2 = SimpleTestRequest { page: None };
 
     // With Some value
-    let params1 = request1.query_params().unwrap();
+    let params1: HashMap<_, _> = request1.query_params().unwrap().into_iter().collect();
     assert_eq!(params1.get("page"), Some(&"5".to_string()));
 
     // With None value
@@ -157,3 +325,1688 @@ fn test_into_reqwest_builder() {
     let builder_result = request.into_reqwest_builder(&client, &base_url);
     assert!(builder_result.is_ok());
 }
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/search", body = "none")]
+struct RepeatedQueryRequest {
+    #[query]
+    tags: Vec<String>,
+
+    #[query(name = "id")]
+    ids: Vec<u64>,
+
+    #[query]
+    page: Option<u32>,
+}
+
+#[test]
+fn test_vec_query_params_repeat_keys() {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = RepeatedQueryRequest {
+        tags: vec!["a".to_string(), "b".to_string()],
+        ids: vec![1, 2],
+        page: Some(3),
+    };
+
+    let params = request.query_params().unwrap();
+    assert_eq!(params.len(), 5);
+
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    let query = built.url().query().unwrap();
+    assert!(query.contains("tags=a&tags=b"));
+    assert!(query.contains("id=1&id=2"));
+    assert!(query.contains("page=3"));
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/secrets", body = "none")]
+struct SensitiveHeaderRequest {
+    #[header(name = "X-Api-Key", sensitive)]
+    api_key: String,
+
+    #[header(name = "X-Trace")]
+    trace: String,
+
+    #[bearer]
+    token: String,
+}
+
+#[test]
+fn test_sensitive_headers_are_marked() {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let built = SensitiveHeaderRequest {
+        api_key: "key-123".to_string(),
+        trace: "abc".to_string(),
+        token: "secret".to_string(),
+    }
+    .into_reqwest_builder(&client, &base_url)
+    .unwrap()
+    .build()
+    .unwrap();
+    let headers = built.headers();
+
+    assert!(headers.get("X-Api-Key").unwrap().is_sensitive());
+    assert!(headers.get("Authorization").unwrap().is_sensitive());
+    assert!(!headers.get("X-Trace").unwrap().is_sensitive());
+    assert!(!format!("{:?}", headers).contains("key-123"));
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(
+    method = "GET",
+    path = "/reports",
+    body = "none",
+    header("X-Api-Version" = "2"),
+    header("X-Client" = "sdk"),
+    header("X-Region" = "eu")
+)]
+struct StaticHeaderRequest {
+    #[header(name = "x-client")]
+    client: Option<String>,
+
+    #[header(name = "X-Region")]
+    region: String,
+}
+
+#[test]
+fn test_static_headers() {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let build = |request: StaticHeaderRequest| {
+        request
+            .into_reqwest_builder(&client, &base_url)
+            .unwrap()
+            .build()
+            .unwrap()
+    };
+
+    let defaults = build(StaticHeaderRequest {
+        client: None,
+        region: "us".to_string(),
+    });
+    assert_eq!(defaults.headers().get("X-Api-Version").unwrap(), "2");
+    assert_eq!(defaults.headers().get("X-Client").unwrap(), "sdk");
+    assert_eq!(defaults.headers().get_all("X-Region").iter().count(), 1);
+    assert_eq!(defaults.headers().get("X-Region").unwrap(), "us");
+
+    let overridden = build(StaticHeaderRequest {
+        client: Some("cli".to_string()),
+        region: "us".to_string(),
+    });
+    assert_eq!(overridden.headers().get_all("X-Client").iter().count(), 1);
+    assert_eq!(overridden.headers().get("X-Client").unwrap(), "cli");
+}
+
+const CLIENT_NAME: &str = "myapp/1.0";
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(
+    method = "GET",
+    path = "/status",
+    body = "none",
+    header_const("X-Client" = CLIENT_NAME),
+    header_const("X-Sdk-Version" = env!("CARGO_PKG_VERSION")),
+    header_const("X-Build" = concat!("sdk-", "rust"))
+)]
+struct ConstHeaderRequest {
+    #[header(name = "X-Build")]
+    build: Option<String>,
+}
+
+#[test]
+fn test_const_headers() {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = ConstHeaderRequest { build: None }
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(request.headers().get("X-Client").unwrap(), "myapp/1.0");
+    assert_eq!(
+        request.headers().get("X-Sdk-Version").unwrap(),
+        env!("CARGO_PKG_VERSION")
+    );
+    assert_eq!(request.headers().get("X-Build").unwrap(), "sdk-rust");
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/search", body = "none")]
+struct OptionalListQueryRequest {
+    #[query(name = "tag")]
+    tags: Option<Vec<String>>,
+
+    #[query(style = "comma")]
+    ids: Option<Vec<u64>>,
+}
+
+#[test]
+fn test_option_vec_query_params() {
+    let none = OptionalListQueryRequest {
+        tags: None,
+        ids: None,
+    };
+    assert_eq!(none.query_params(), None);
+
+    let some = OptionalListQueryRequest {
+        tags: Some(vec!["a".to_string(), "b".to_string()]),
+        ids: Some(vec![1, 2]),
+    };
+    assert_eq!(
+        some.query_params(),
+        Some(vec![
+            ("tag".to_string(), "a".to_string()),
+            ("tag".to_string(), "b".to_string()),
+            ("ids".to_string(), "1,2".to_string()),
+        ])
+    );
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/items", body = "none")]
+struct QueryStyleRequest {
+    #[query(style = "comma")]
+    ids: Vec<u64>,
+
+    #[query(style = "comma", name = "fields")]
+    fields: [&'static str; 2],
+
+    #[query(style = "repeat", name = "tag")]
+    tags: [&'static str; 2],
+
+    #[query(style = "comma")]
+    empty: Vec<u64>,
+}
+
+#[test]
+fn test_query_styles_comma_and_repeat() {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = QueryStyleRequest {
+        ids: vec![1, 2, 3],
+        fields: ["name", "email"],
+        tags: ["a", "b"],
+        empty: Vec::new(),
+    };
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        built.url().query(),
+        Some("ids=1%2C2%2C3&fields=name%2Cemail&tag=a&tag=b")
+    );
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/items", body = "none")]
+struct PrimitiveQueryRequest {
+    #[query]
+    offset: usize,
+
+    #[query]
+    port: u16,
+
+    #[query]
+    level: Option<i8>,
+
+    #[query]
+    grade: char,
+}
+
+#[test]
+fn test_primitive_query_params() {
+    let request = PrimitiveQueryRequest {
+        offset: 40,
+        port: 8080,
+        level: Some(-1),
+        grade: 'A',
+    };
+
+    assert_eq!(
+        request.query_params(),
+        Some(vec![
+            ("offset".to_string(), "40".to_string()),
+            ("port".to_string(), "8080".to_string()),
+            ("level".to_string(), "-1".to_string()),
+            ("grade".to_string(), "A".to_string()),
+        ])
+    );
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(
+    method = "GET",
+    path = "/repos/{owner}/issues",
+    body = "none",
+    query = "serde"
+)]
+#[serde(rename_all = "camelCase")]
+struct ListIssuesQuery {
+    #[path_param]
+    owner: String,
+
+    #[header(name = "Authorization")]
+    token: String,
+
+    per_page: u32,
+    #[serde(rename = "q")]
+    search: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    since: Option<String>,
+    #[serde(skip)]
+    #[allow(dead_code)]
+    local_note: String,
+}
+
+#[test]
+fn test_serde_query_style() {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = ListIssuesQuery {
+        owner: "rust-lang".to_string(),
+        token: "Bearer abc".to_string(),
+        per_page: 50,
+        search: "label:bug is:open".to_string(),
+        since: None,
+        local_note: "not sent".to_string(),
+    };
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(
+        built.url().as_str(),
+        "https://api.example.com/repos/rust-lang/issues?perPage=50&q=label%3Abug+is%3Aopen"
+    );
+    assert_eq!(built.headers().get("Authorization").unwrap(), "Bearer abc");
+    assert!(built.body().is_none());
+}
+
+#[cfg(feature = "serde_qs")]
+#[derive(Serialize)]
+struct UserFilter {
+    status: String,
+    role: String,
+}
+
+#[cfg(feature = "serde_qs")]
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/users", query = "qs")]
+struct SearchUsersQuery {
+    filter: UserFilter,
+    page: u32,
+}
+
+#[cfg(feature = "serde_qs")]
+#[test]
+fn test_qs_query_style() {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = SearchUsersQuery {
+        filter: UserFilter {
+            status: "active".to_string(),
+            role: "admin".to_string(),
+        },
+        page: 2,
+    };
+    assert_eq!(
+        request.query_params(),
+        Some(vec![
+            ("filter[status]".to_string(), "active".to_string()),
+            ("filter[role]".to_string(), "admin".to_string()),
+            ("page".to_string(), "2".to_string()),
+        ])
+    );
+
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(
+        built.url().query(),
+        Some("filter%5Bstatus%5D=active&filter%5Brole%5D=admin&page=2")
+    );
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/search")]
+struct RequiredQueryRequest {
+    #[query(required)]
+    q: String,
+
+    #[query(name = "lang", required)]
+    language: Option<String>,
+
+    #[query]
+    page: Option<u32>,
+}
+
+#[test]
+fn test_required_query_params() {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = RequiredQueryRequest {
+        q: String::new(),
+        language: Some("en".to_string()),
+        page: None,
+    };
+    assert_eq!(
+        request
+            .into_reqwest_builder(&client, &base_url)
+            .expect_err("an empty required parameter should be rejected"),
+        ReqwestBuilderError::QueryError {
+            key: "q".to_string(),
+            reason: "Required query parameter is missing or empty".to_string(),
+        }
+    );
+
+    let request = RequiredQueryRequest {
+        q: "rust".to_string(),
+        language: None,
+        page: None,
+    };
+    assert!(matches!(
+        request.into_reqwest_builder(&client, &base_url),
+        Err(ReqwestBuilderError::QueryError { key, .. }) if key == "lang"
+    ));
+
+    let request = RequiredQueryRequest {
+        q: "rust".to_string(),
+        language: Some("en".to_string()),
+        page: None,
+    };
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(built.url().query(), Some("q=rust&lang=en"));
+}
+
+#[derive(Serialize)]
+struct Pagination {
+    page: u32,
+    per_page: u32,
+}
+
+impl reqwest_builder::IntoQueryParams for Pagination {
+    fn to_query_params(&self) -> Vec<(String, String)> {
+        vec![
+            ("page".to_string(), self.page.to_string()),
+            ("per_page".to_string(), self.per_page.to_string()),
+        ]
+    }
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/repos/{owner}/issues")]
+struct ListIssuesRequest {
+    #[path_param]
+    owner: String,
+
+    #[query]
+    state: String,
+
+    #[query(flatten)]
+    pagination: Pagination,
+}
+
+#[test]
+fn test_flattened_query_params() {
+    let request = ListIssuesRequest {
+        owner: "rust-lang".to_string(),
+        state: "open".to_string(),
+        pagination: Pagination {
+            page: 3,
+            per_page: 50,
+        },
+    };
+
+    assert_eq!(
+        request.query_params(),
+        Some(vec![
+            ("state".to_string(), "open".to_string()),
+            ("page".to_string(), "3".to_string()),
+            ("per_page".to_string(), "50".to_string()),
+        ])
+    );
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(built.url().query(), Some("state=open&page=3&per_page=50"));
+}
+
+#[derive(Serialize)]
+enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+impl std::fmt::Display for SortOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SortOrder::Ascending => write!(f, "asc"),
+            SortOrder::Descending => write!(f, "desc"),
+        }
+    }
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/articles", body = "none")]
+struct DisplayQueryRequest {
+    #[query]
+    order: SortOrder,
+
+    #[query]
+    then_by: Option<SortOrder>,
+
+    #[query]
+    fallback: Option<SortOrder>,
+}
+
+#[test]
+fn test_display_query_params() {
+    let request = DisplayQueryRequest {
+        order: SortOrder::Descending,
+        then_by: Some(SortOrder::Ascending),
+        fallback: None,
+    };
+
+    assert_eq!(
+        request.query_params(),
+        Some(vec![
+            ("order".to_string(), "desc".to_string()),
+            ("then_by".to_string(), "asc".to_string()),
+        ])
+    );
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/pages", body = "none")]
+struct RawQueryRequest {
+    #[query(raw)]
+    cursor: String,
+
+    #[query(raw)]
+    after: Option<String>,
+
+    #[query]
+    q: String,
+}
+
+#[test]
+fn test_raw_query_params_are_not_reencoded() {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = RawQueryRequest {
+        cursor: "abc%2Fdef%3D%3D".to_string(),
+        after: None,
+        q: "a/b".to_string(),
+    };
+    assert_eq!(
+        request.query_params(),
+        Some(vec![("q".to_string(), "a/b".to_string())])
+    );
+
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(
+        built.url().as_str(),
+        "https://api.example.com/pages?q=a%2Fb&cursor=abc%2Fdef%3D%3D"
+    );
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/locks", body = "none")]
+struct DurationQueryRequest {
+    #[query]
+    timeout: std::time::Duration,
+
+    #[query]
+    ttl: Option<std::time::Duration>,
+
+    #[query]
+    grace: Option<std::time::Duration>,
+}
+
+#[test]
+fn test_duration_query_params_as_seconds() {
+    let request = DurationQueryRequest {
+        timeout: std::time::Duration::from_secs(30),
+        ttl: Some(std::time::Duration::from_millis(1500)),
+        grace: None,
+    };
+
+    assert_eq!(
+        request.query_params(),
+        Some(vec![
+            ("timeout".to_string(), "30".to_string()),
+            ("ttl".to_string(), "1.5".to_string()),
+        ])
+    );
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "PUT", path = "/notes/{id}", body = "text")]
+struct TextTestRequest {
+    #[path_param]
+    id: u64,
+
+    note: String,
+}
+
+#[test]
+fn test_text_body_request() {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = TextTestRequest {
+        id: 7,
+        note: "hello".to_string(),
+    };
+
+    assert_eq!(request.body(), RequestBody::Text);
+    assert_eq!(request.text_body(), Some("hello".to_string()));
+
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(built.body().unwrap().as_bytes(), Some("hello".as_bytes()));
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "POST", path = "/blobs", body = "bytes")]
+struct BytesTestRequest {
+    #[header(name = "Content-Type")]
+    content_type: String,
+
+    data: Vec<u8>,
+}
+
+#[test]
+fn test_bytes_body_request() {
+    let request = BytesTestRequest {
+        content_type: "application/octet-stream".to_string(),
+        data: vec![1, 2, 3],
+    };
+
+    assert_eq!(request.body(), RequestBody::Bytes);
+    assert_eq!(request.bytes_body(), Some(vec![1, 2, 3]));
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(
+    method = "GET",
+    path = "/files/{name}/versions/{version}",
+    body = "none"
+)]
+struct EncodedPathRequest {
+    #[path_param]
+    name: String,
+
+    #[path_param]
+    version: u32,
+}
+
+#[test]
+fn test_path_params_are_percent_encoded() {
+    let request = EncodedPathRequest {
+        name: "my file/v2?#%".to_string(),
+        version: 3,
+    };
+
+    assert_eq!(
+        request.endpoint(),
+        "/files/my%20file%2Fv2%3F%23%25/versions/3"
+    );
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(
+        built.url().path(),
+        "/files/my%20file%2Fv2%3F%23%25/versions/3"
+    );
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/resources/{type}/{id}")]
+struct RawIdentPathRequest {
+    #[path_param]
+    r#type: String,
+
+    #[path_param]
+    id: u32,
+}
+
+#[test]
+fn test_raw_identifier_path_param() {
+    let request = RawIdentPathRequest {
+        r#type: "widget".to_string(),
+        id: 7,
+    };
+    assert_eq!(request.endpoint(), "/resources/widget/7");
+}
+
+#[test]
+fn test_dot_segment_path_params_are_rejected() {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    for name in [".", ".."] {
+        let request = EncodedPathRequest {
+            name: name.to_string(),
+            version: 3,
+        };
+        match request.into_reqwest_builder(&client, &base_url) {
+            Err(ReqwestBuilderError::UrlError(message)) => {
+                assert!(message.contains("dot-segment"), "{message}");
+            }
+            other => panic!(
+                "Expected UrlError for {name:?}, got {:?}",
+                other.map(|_| ())
+            ),
+        }
+    }
+
+    // Dots within a segment are ordinary characters
+    let request = EncodedPathRequest {
+        name: "...".to_string(),
+        version: 3,
+    };
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(built.url().path(), "/files/.../versions/3");
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/items", body = "none")]
+struct NoneQueryRequest {
+    #[query(skip_if_none)]
+    page: Option<u32>,
+
+    #[query(include_none, name = "flag")]
+    enabled: Option<bool>,
+}
+
+#[test]
+fn test_query_skip_if_none_and_include_none() {
+    let request = NoneQueryRequest {
+        page: None,
+        enabled: None,
+    };
+    let params: HashMap<_, _> = request.query_params().unwrap().into_iter().collect();
+    assert!(!params.contains_key("page"));
+    assert_eq!(params.get("flag"), Some(&String::new()));
+
+    let request = NoneQueryRequest {
+        page: Some(2),
+        enabled: Some(true),
+    };
+    let params: HashMap<_, _> = request.query_params().unwrap().into_iter().collect();
+    assert_eq!(params.get("page"), Some(&"2".to_string()));
+    assert_eq!(params.get("flag"), Some(&"true".to_string()));
+}
+
+fn hex_color(rgb: &(u8, u8, u8)) -> String {
+    format!("{:02x}{:02x}{:02x}", rgb.0, rgb.1, rgb.2)
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/palette", body = "none")]
+struct CustomFormatQueryRequest {
+    #[query(with = "hex_color")]
+    color: (u8, u8, u8),
+}
+
+#[test]
+fn test_query_with_custom_formatter() {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = CustomFormatQueryRequest {
+        color: (255, 128, 0),
+    };
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(built.url().query(), Some("color=ff8000"));
+}
+
+#[cfg(feature = "chrono")]
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/events", body = "none")]
+struct ChronoQueryRequest {
+    #[query]
+    since: chrono::DateTime<chrono::Utc>,
+    #[query]
+    until: Option<chrono::DateTime<chrono::Utc>>,
+    #[query]
+    day: Option<chrono::NaiveDate>,
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_chrono_query_params() {
+    use chrono::TimeZone;
+
+    let request = ChronoQueryRequest {
+        since: chrono::Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap(),
+        until: Some(chrono::Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap()),
+        day: chrono::NaiveDate::from_ymd_opt(2024, 3, 15),
+    };
+    assert_eq!(
+        request.query_params().unwrap(),
+        vec![
+            ("since".to_string(), "2024-01-02T03:04:05+00:00".to_string()),
+            ("until".to_string(), "2024-02-01T00:00:00+00:00".to_string()),
+            ("day".to_string(), "2024-03-15".to_string()),
+        ]
+    );
+
+    let request = ChronoQueryRequest {
+        until: None,
+        day: None,
+        ..request
+    };
+    assert_eq!(request.query_params().unwrap().len(), 1);
+}
+
+#[cfg(feature = "chrono")]
+type Timestamp = chrono::DateTime<chrono::Utc>;
+
+#[cfg(feature = "chrono")]
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/events", body = "none")]
+struct AliasedChronoQueryRequest {
+    #[query]
+    until: Option<Timestamp>,
+    #[query]
+    day: ::std::option::Option<chrono::NaiveDate>,
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_optional_chrono_query_params_through_aliases_and_helper() {
+    use chrono::TimeZone;
+
+    let until = chrono::Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap();
+    let day = chrono::NaiveDate::from_ymd_opt(2024, 3, 15);
+    let request = AliasedChronoQueryRequest {
+        until: Some(until),
+        day,
+    };
+    assert_eq!(
+        request.query_params().unwrap(),
+        vec![
+            ("until".to_string(), "2024-02-01T00:00:00+00:00".to_string()),
+            ("day".to_string(), "2024-03-15".to_string()),
+        ]
+    );
+
+    // `query_param_helper` uses the same forms, and `None` adds nothing
+    let mut params = Vec::new();
+    reqwest_builder::query_param_helper(&Some(until), "until", &mut params);
+    reqwest_builder::query_param_helper(&day, "day", &mut params);
+    reqwest_builder::query_param_helper(&None::<chrono::NaiveDate>, "skipped", &mut params);
+    assert_eq!(
+        params,
+        vec![
+            ("until".to_string(), "2024-02-01T00:00:00+00:00".to_string()),
+            ("day".to_string(), "2024-03-15".to_string()),
+        ]
+    );
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(
+    method = "GET",
+    path = "/camel",
+    body = "none",
+    rename_all = "camelCase"
+)]
+struct CamelCaseRequest {
+    #[query]
+    page_size: Option<u32>,
+
+    #[query(name = "q")]
+    search_term: Option<String>,
+
+    #[header]
+    request_id: String,
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(
+    method = "GET",
+    path = "/snake",
+    body = "none",
+    rename_all = "snake_case"
+)]
+struct SnakeCaseRequest {
+    #[query]
+    page_size: Option<u32>,
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(
+    method = "GET",
+    path = "/kebab",
+    body = "none",
+    rename_all = "kebab-case"
+)]
+struct KebabCaseRequest {
+    #[query]
+    page_size: Option<u32>,
+
+    #[header]
+    x_request_id: String,
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(
+    method = "GET",
+    path = "/pascal",
+    body = "none",
+    rename_all = "PascalCase"
+)]
+struct PascalCaseRequest {
+    #[query]
+    page_size: Option<u32>,
+}
+
+#[test]
+fn test_rename_all_query_and_header_names() {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let camel = CamelCaseRequest {
+        page_size: Some(10),
+        search_term: Some("rust".to_string()),
+        request_id: "abc".to_string(),
+    };
+    let params: HashMap<_, _> = camel.query_params().unwrap().into_iter().collect();
+    assert_eq!(params.get("pageSize"), Some(&"10".to_string()));
+    assert_eq!(params.get("q"), Some(&"rust".to_string()));
+    let built = camel
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(built.headers().get("requestId").unwrap(), "abc");
+
+    let snake = SnakeCaseRequest { page_size: Some(1) };
+    assert_eq!(snake.query_params().unwrap()[0].0, "page_size");
+
+    let kebab = KebabCaseRequest {
+        page_size: Some(1),
+        x_request_id: "abc".to_string(),
+    };
+    assert_eq!(kebab.query_params().unwrap()[0].0, "page-size");
+    let built = kebab
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(built.headers().get("x-request-id").unwrap(), "abc");
+
+    let pascal = PascalCaseRequest { page_size: Some(1) };
+    assert_eq!(pascal.query_params().unwrap()[0].0, "PageSize");
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/typed-headers", body = "none")]
+struct TypedHeadersRequest {
+    #[header(name = "X-Trace-Id")]
+    trace_id: Option<String>,
+
+    #[header(name = "X-Retry-Count")]
+    retry_count: u32,
+
+    #[header(name = "X-Ratio")]
+    ratio: Option<f64>,
+}
+
+#[test]
+fn test_non_string_headers() {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = TypedHeadersRequest {
+        trace_id: None,
+        retry_count: 3,
+        ratio: Some(0.5),
+    };
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert!(built.headers().get("X-Trace-Id").is_none());
+    assert_eq!(built.headers().get("X-Retry-Count").unwrap(), "3");
+    assert_eq!(built.headers().get("X-Ratio").unwrap(), "0.5");
+
+    let request = TypedHeadersRequest {
+        trace_id: Some("abc".to_string()),
+        retry_count: 0,
+        ratio: None,
+    };
+    let headers = request.headers().unwrap();
+    assert_eq!(headers.trace_id, Some("abc".to_string()));
+    assert_eq!(headers.ratio, None);
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "POST", path = "/documents", body = "multipart")]
+struct MultipartTestRequest {
+    title: String,
+
+    description: Option<String>,
+
+    #[file]
+    document: FileUpload,
+}
+
+#[tokio::test]
+async fn test_multipart_request_with_text_and_file() {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/documents"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse(&server.uri()).unwrap();
+
+    let request = MultipartTestRequest {
+        title: "Report".to_string(),
+        description: None,
+        document: FileUpload::from_bytes(
+            "report.txt".to_string(),
+            b"file contents".to_vec(),
+            Some("text/plain".to_string()),
+        ),
+    };
+
+    assert_eq!(request.body(), RequestBody::Multipart);
+    request.send(&client, &base_url).await.unwrap();
+
+    let received = server.received_requests().await.unwrap();
+    let content_type = received[0].headers.get("content-type").unwrap();
+    assert!(
+        content_type
+            .to_str()
+            .unwrap()
+            .starts_with("multipart/form-data")
+    );
+
+    let body = String::from_utf8_lossy(&received[0].body);
+    assert!(body.contains("name=\"title\""));
+    assert!(body.contains("Report"));
+    assert!(!body.contains("name=\"description\""));
+    assert!(body.contains("name=\"document\"; filename=\"report.txt\""));
+    assert!(body.contains("Content-Type: text/plain"));
+    assert!(body.contains("file contents"));
+}
+
+#[test]
+fn test_multipart_invalid_mime_type_is_an_error() {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = MultipartTestRequest {
+        title: "Report".to_string(),
+        description: None,
+        document: FileUpload::from_bytes(
+            "report.txt".to_string(),
+            b"file contents".to_vec(),
+            Some("not a mime type".to_string()),
+        ),
+    };
+
+    let err = request
+        .into_reqwest_builder(&client, &base_url)
+        .expect_err("an invalid MIME type should fail the multipart form");
+    assert!(
+        matches!(&err, ReqwestBuilderError::MultipartError(message) if message.contains("report.txt")),
+        "unexpected error: {err:?}"
+    );
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "PATCH", path = "/profile", body = "multipart")]
+struct UpdateProfileRequest {
+    display_name: String,
+
+    #[file]
+    avatar: Option<FileUpload>,
+}
+
+#[tokio::test]
+async fn test_multipart_optional_file() {
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("PATCH"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse(&server.uri()).unwrap();
+
+    let without_avatar = UpdateProfileRequest {
+        display_name: "Ada".to_string(),
+        avatar: None,
+    };
+    without_avatar.send(&client, &base_url).await.unwrap();
+
+    let with_avatar = UpdateProfileRequest {
+        display_name: "Ada".to_string(),
+        avatar: Some(FileUpload::from_bytes(
+            "avatar.png".to_string(),
+            vec![0x89, 0x50, 0x4e, 0x47],
+            Some("image/png".to_string()),
+        )),
+    };
+    with_avatar.send(&client, &base_url).await.unwrap();
+
+    let received = server.received_requests().await.unwrap();
+    let first = String::from_utf8_lossy(&received[0].body);
+    assert!(first.contains("name=\"display_name\""));
+    assert!(!first.contains("name=\"avatar\""));
+
+    let second = String::from_utf8_lossy(&received[1].body);
+    assert!(second.contains("name=\"avatar\"; filename=\"avatar.png\""));
+    assert!(second.contains("Content-Type: image/png"));
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(
+    method = "POST",
+    path = "/profiles",
+    body = "multipart",
+    rename_all = "camelCase"
+)]
+struct CreateProfileRequest {
+    display_name: String,
+
+    r#type: String,
+
+    #[file]
+    profile_photo: FileUpload,
+}
+
+#[tokio::test]
+async fn test_multipart_part_names_follow_rename_all() {
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse(&server.uri()).unwrap();
+
+    let request = CreateProfileRequest {
+        display_name: "Ada".to_string(),
+        r#type: "admin".to_string(),
+        profile_photo: FileUpload::from_bytes(
+            "ada.png".to_string(),
+            vec![0x89, 0x50],
+            Some("image/png".to_string()),
+        ),
+    };
+    request.send(&client, &base_url).await.unwrap();
+
+    let received = server.received_requests().await.unwrap();
+    let body = String::from_utf8_lossy(&received[0].body);
+    assert!(body.contains("name=\"displayName\""));
+    assert!(body.contains("name=\"type\""));
+    assert!(body.contains("name=\"profilePhoto\"; filename=\"ada.png\""));
+    assert!(!body.contains("r#type"));
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "POST", path = "/imports", body = "multipart")]
+struct ImportRequest {
+    #[serde(rename = "sheetTitle")]
+    title: String,
+
+    #[file(name = "upload")]
+    spreadsheet: FileUpload,
+}
+
+#[tokio::test]
+async fn test_multipart_renamed_file_part() {
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse(&server.uri()).unwrap();
+
+    let request = ImportRequest {
+        title: "Users".to_string(),
+        spreadsheet: FileUpload::from_bytes(
+            "users.csv".to_string(),
+            b"id,name".to_vec(),
+            Some("text/csv".to_string()),
+        ),
+    };
+    request.send(&client, &base_url).await.unwrap();
+
+    let received = server.received_requests().await.unwrap();
+    let body = String::from_utf8_lossy(&received[0].body);
+    assert!(
+        body.contains("Content-Disposition: form-data; name=\"upload\"; filename=\"users.csv\"")
+    );
+    assert!(!body.contains("name=\"spreadsheet\""));
+    assert!(body.contains("Content-Disposition: form-data; name=\"sheetTitle\""));
+    assert!(!body.contains("name=\"title\""));
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(
+    method = "POST",
+    path = "/attachments",
+    body = "multipart",
+    max_multipart_bytes = 8
+)]
+struct LimitedUploadRequest {
+    #[file]
+    first: FileUpload,
+
+    #[file]
+    second: Option<FileUpload>,
+}
+
+#[test]
+fn test_multipart_size_limit() {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let file =
+        |name: &str, size: usize| FileUpload::from_bytes(name.to_string(), vec![0; size], None);
+
+    let within_limit = LimitedUploadRequest {
+        first: file("a.bin", 4),
+        second: Some(file("b.bin", 4)),
+    };
+    assert!(
+        within_limit
+            .into_reqwest_builder(&client, &base_url)
+            .is_ok()
+    );
+
+    let over_limit = LimitedUploadRequest {
+        first: file("a.bin", 5),
+        second: Some(file("b.bin", 5)),
+    };
+    assert_eq!(
+        over_limit.into_reqwest_builder(&client, &base_url).err(),
+        Some(reqwest_builder::ReqwestBuilderError::InvalidRequest(
+            "Multipart files total 10 bytes, exceeding the limit of 8 bytes".to_string()
+        ))
+    );
+}
+
+#[derive(Serialize)]
+struct Event {
+    name: String,
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "POST", path = "/events", body = "ndjson")]
+struct NdjsonTestRequest {
+    events: Vec<Event>,
+}
+
+#[test]
+fn test_ndjson_body_request() {
+    let request = NdjsonTestRequest {
+        events: vec![
+            Event {
+                name: "a".to_string(),
+            },
+            Event {
+                name: "b".to_string(),
+            },
+        ],
+    };
+
+    assert_eq!(request.body(), RequestBody::Ndjson);
+    assert_eq!(
+        request.ndjson_records(),
+        Some(vec![
+            serde_json::json!({ "name": "a" }),
+            serde_json::json!({ "name": "b" }),
+        ])
+    );
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/ordered", body = "none")]
+struct OrderedQueryRequest {
+    #[query]
+    zeta: u32,
+
+    #[query]
+    alpha: Vec<String>,
+
+    #[query]
+    middle: Option<bool>,
+
+    #[query]
+    beta: String,
+}
+
+#[test]
+fn test_query_params_follow_field_order() {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    for _ in 0..10 {
+        let request = OrderedQueryRequest {
+            zeta: 1,
+            alpha: vec!["x".to_string(), "y".to_string()],
+            middle: Some(true),
+            beta: "b".to_string(),
+        };
+        let built = request
+            .into_reqwest_builder(&client, &base_url)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(
+            built.url().query(),
+            Some("zeta=1&alpha=x&alpha=y&middle=true&beta=b")
+        );
+    }
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/me", body = "none")]
+struct BearerTestRequest {
+    #[bearer]
+    token: String,
+}
+
+#[test]
+fn test_bearer_token_header() {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = BearerTestRequest {
+        token: "secret123".to_string(),
+    };
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let authorization = built.headers().get("Authorization").unwrap();
+    assert!(authorization.to_str().unwrap().starts_with("Bearer "));
+    assert_eq!(authorization, "Bearer secret123");
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/account", body = "none")]
+struct BasicAuthTestRequest {
+    #[basic_auth(username)]
+    user: String,
+    #[basic_auth(password)]
+    pass: String,
+}
+
+#[test]
+fn test_basic_auth_header() {
+    use base64::Engine;
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = BasicAuthTestRequest {
+        user: "user".to_string(),
+        pass: "pass".to_string(),
+    };
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let authorization = built.headers().get("Authorization").unwrap();
+    let encoded = authorization
+        .to_str()
+        .unwrap()
+        .strip_prefix("Basic ")
+        .unwrap();
+    let decoded = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .unwrap();
+    assert_eq!(decoded, b"user:pass");
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/session", body = "none")]
+struct MultiValueHeaderRequest {
+    #[header(name = "Cookie")]
+    cookies: Vec<String>,
+}
+
+#[test]
+fn test_multi_value_header() {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = MultiValueHeaderRequest {
+        cookies: vec!["a=1".to_string(), "b=2".to_string()],
+    };
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let cookies: Vec<_> = built.headers().get_all("Cookie").iter().collect();
+    assert_eq!(cookies, vec!["a=1", "b=2"]);
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "PURGE", path = "/cache/{key}", body = "none")]
+struct PurgeRequest {
+    #[path_param]
+    key: String,
+}
+
+#[test]
+fn test_extension_method() {
+    let request = PurgeRequest {
+        key: "home".to_string(),
+    };
+
+    assert_eq!(request.method().as_str(), "PURGE");
+    assert_eq!(
+        request.method(),
+        http::Method::from_bytes(b"PURGE").unwrap()
+    );
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/users/{id}/posts/{post}", body = "none")]
+struct RenamedPathParamRequest {
+    #[path_param(name = "id")]
+    user_id: u64,
+    #[path_param(name = "post")]
+    post_id: String,
+}
+
+#[test]
+fn test_renamed_path_param() {
+    let request = RenamedPathParamRequest {
+        user_id: 7,
+        post_id: "hello world".to_string(),
+    };
+
+    assert_eq!(request.endpoint(), "/users/7/posts/hello%20world");
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[serde(untagged)]
+enum ItemOperation {
+    #[request(method = "GET", path = "/items/{id}", body = "none")]
+    Get {
+        #[path_param]
+        id: u64,
+    },
+    #[request(method = "POST", path = "/items")]
+    Create { name: String },
+}
+
+#[test]
+fn test_enum_request_variants() {
+    let get = ItemOperation::Get { id: 5 };
+    assert_eq!(get.method(), http::Method::GET);
+    assert_eq!(get.endpoint(), "/items/5");
+    assert_eq!(get.body(), RequestBody::None);
+
+    let create = ItemOperation::Create {
+        name: "widget".to_string(),
+    };
+    assert_eq!(create.method(), http::Method::POST);
+    assert_eq!(create.endpoint(), "/items");
+    assert_eq!(create.body(), RequestBody::Json);
+
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+    let built = create
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(built.url().as_str(), "https://api.example.com/items");
+    assert_eq!(
+        built.body().unwrap().as_bytes().unwrap(),
+        br#"{"name":"widget"}"#
+    );
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+enum TaggedItemOperation {
+    #[request(method = "PUT", path = "/items/{id}")]
+    Rename {
+        #[path_param]
+        id: u64,
+        name: String,
+    },
+    #[request(method = "POST", path = "/items/{id}/archive")]
+    Archive {
+        #[path_param]
+        id: u64,
+    },
+}
+
+#[test]
+fn test_externally_tagged_enum_body_leaves_out_path_fields() {
+    let rename = TaggedItemOperation::Rename {
+        id: 5,
+        name: "widget".to_string(),
+    };
+    assert_eq!(rename.endpoint(), "/items/5");
+    assert_eq!(
+        rename.serialize_body().unwrap(),
+        serde_json::json!({"Rename": {"name": "widget"}})
+    );
+
+    let archive = TaggedItemOperation::Archive { id: 7 };
+    assert_eq!(archive.endpoint(), "/items/7/archive");
+    assert_eq!(
+        archive.serialize_body().unwrap(),
+        serde_json::json!({"Archive": {}})
+    );
+}
+
+#[derive(Serialize)]
+struct Inner {
+    name: String,
+    count: u32,
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "PUT", path = "/items/{id}")]
+struct WholeBodyRequest {
+    #[path_param]
+    id: u64,
+
+    #[body]
+    payload: Inner,
+}
+
+#[test]
+fn test_single_body_field_is_whole_body() {
+    let request = WholeBodyRequest {
+        id: 3,
+        payload: Inner {
+            name: "widget".to_string(),
+            count: 2,
+        },
+    };
+
+    assert_eq!(
+        request.serialize_body().unwrap(),
+        serde_json::json!({ "name": "widget", "count": 2 })
+    );
+}
+
+#[derive(Serialize)]
+struct Address {
+    city: String,
+    country: String,
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "PUT", path = "/profiles/{id}")]
+struct EditProfileRequest {
+    #[path_param]
+    id: u64,
+
+    #[query]
+    notify: bool,
+
+    #[header(name = "X-Request-Id")]
+    request_id: String,
+
+    #[serde(rename = "displayName")]
+    display_name: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bio: Option<String>,
+
+    #[serde(flatten)]
+    address: Address,
+}
+
+#[test]
+fn test_body_view_keeps_serde_attributes() {
+    let request = EditProfileRequest {
+        id: 7,
+        notify: true,
+        request_id: "req-1".to_string(),
+        display_name: "Ada".to_string(),
+        bio: None,
+        address: Address {
+            city: "London".to_string(),
+            country: "UK".to_string(),
+        },
+    };
+
+    assert_eq!(
+        request.serialize_body().unwrap(),
+        serde_json::json!({
+            "displayName": "Ada",
+            "city": "London",
+            "country": "UK",
+        })
+    );
+}
+
+#[derive(Serialize)]
+struct UserPayload {
+    name: String,
+    email: String,
+}
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "POST", path = "/u")]
+struct CreateUserNewtype(UserPayload);
+
+#[test]
+fn test_newtype_struct_body() {
+    let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    let base_url = Url::parse("https://api.example.com").unwrap();
+
+    let request = CreateUserNewtype(UserPayload {
+        name: "Ada".to_string(),
+        email: "ada@example.com".to_string(),
+    });
+    assert_eq!(request.method(), http::Method::POST);
+    assert_eq!(request.endpoint(), "/u");
+
+    let built = request
+        .into_reqwest_builder(&client, &base_url)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(built.url().as_str(), "https://api.example.com/u");
+    assert_eq!(
+        built.headers().get("Content-Type").unwrap(),
+        "application/json"
+    );
+    let body: serde_json::Value =
+        serde_json::from_slice(built.body().unwrap().as_bytes().unwrap()).unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({ "name": "Ada", "email": "ada@example.com" })
+    );
+}
+
+#[test]
+fn test_compile_errors() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
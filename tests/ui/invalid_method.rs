@@ -0,0 +1,10 @@
+use reqwest_builder::IntoReqwestBuilder;
+use serde::Serialize;
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "G ET", path = "/users")]
+struct InvalidMethodRequest {
+    name: String,
+}
+
+fn main() {}
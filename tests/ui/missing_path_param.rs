@@ -0,0 +1,11 @@
+use reqwest_builder::IntoReqwestBuilder;
+use serde::Serialize;
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/users/{id}/posts/{post_id}", body = "none")]
+struct MissingPathParamRequest {
+    #[path_param]
+    id: u64,
+}
+
+fn main() {}
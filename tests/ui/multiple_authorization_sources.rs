@@ -0,0 +1,14 @@
+use reqwest_builder::IntoReqwestBuilder;
+use serde::Serialize;
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/me")]
+struct MultipleAuthorizationRequest {
+    #[bearer]
+    token: String,
+
+    #[header(name = "Authorization")]
+    authorization: String,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use reqwest_builder::IntoReqwestBuilder;
+use serde::Serialize;
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "POST", path = "/items")]
+struct MultipleBodyRequest {
+    #[body]
+    first: String,
+
+    #[body]
+    second: String,
+}
+
+fn main() {}
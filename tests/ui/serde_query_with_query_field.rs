@@ -0,0 +1,13 @@
+use reqwest_builder::IntoReqwestBuilder;
+use serde::Serialize;
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/search", body = "none", query = "serde")]
+struct SearchRequest {
+    #[query]
+    page: u32,
+
+    q: String,
+}
+
+fn main() {}
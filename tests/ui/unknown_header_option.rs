@@ -0,0 +1,11 @@
+use reqwest_builder::IntoReqwestBuilder;
+use serde::Serialize;
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/me")]
+struct ProfileRequest {
+    #[header(name = "X-Api-Key", sensitiv)]
+    api_key: String,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use reqwest_builder::IntoReqwestBuilder;
+use serde::Serialize;
+
+#[derive(Serialize, IntoReqwestBuilder)]
+#[request(method = "GET", path = "/users/{id}", body = "none")]
+struct UnusedPathParamRequest {
+    #[path_param]
+    id: u64,
+
+    #[path_param]
+    post_id: u64,
+}
+
+fn main() {}